@@ -0,0 +1,41 @@
+extern crate coord_transforms;
+extern crate nalgebra as na;
+
+use coord_transforms::geo;
+use coord_transforms::structs::geo_ellipsoid;
+use na::Vector3;
+use std::time::Instant;
+
+const POINTS: usize = 1_000_000;
+
+/// lla2ecef as written before the ellipsoid cached e^2 and b^2 / a^2
+fn lla2ecef_recomputed(lla_vec: &Vector3<f64>, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> Vector3<f64> {
+    let n = ellipsoid.get_semi_major_axis() / (1.0 - ellipsoid.get_first_ecc().powi(2) * lla_vec.x.sin().powi(2)).sqrt();
+    Vector3::new((n + lla_vec.z) * lla_vec.x.cos() * lla_vec.y.cos(),
+                 (n + lla_vec.z) * lla_vec.x.cos() * lla_vec.y.sin(),
+                 ((ellipsoid.get_semi_minor_axis().powi(2) / ellipsoid.get_semi_major_axis().powi(2)) * n + lla_vec.z) * lla_vec.x.sin())
+}
+
+fn main() {
+    let ellipsoid = geo_ellipsoid::GeoEllipsoid::wgs84();
+    let points: Vec<Vector3<f64>> = (0..POINTS)
+        .map(|i| Vector3::new(((i % 1800) as f64 / 10.0 - 90.0).to_radians(), ((i % 3600) as f64 / 10.0 - 180.0).to_radians(), (i % 1000) as f64))
+        .collect();
+
+    let start = Instant::now();
+    let recomputed: Vec<Vector3<f64>> = points.iter().map(|p| lla2ecef_recomputed(p, &ellipsoid)).collect();
+    let recomputed_time = start.elapsed();
+
+    let start = Instant::now();
+    let cached: Vec<Vector3<f64>> = points.iter().map(|p| geo::lla2ecef(p, &ellipsoid)).collect();
+    let cached_time = start.elapsed();
+
+    let start = Instant::now();
+    let alt_sum: f64 = cached.iter().map(|p| geo::ecef2lla(p, &ellipsoid).z).sum();
+    let inverse_time = start.elapsed();
+
+    assert_eq!(recomputed, cached);
+    println!("lla2ecef, recomputed e^2:    {:?} per call", recomputed_time / POINTS as u32);
+    println!("lla2ecef, cached e^2:        {:?} per call", cached_time / POINTS as u32);
+    println!("ecef2lla, cached e^2, e'^2:  {:?} per call (altitude sum {})", inverse_time / POINTS as u32, alt_sum);
+}
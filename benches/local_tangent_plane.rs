@@ -0,0 +1,44 @@
+extern crate coord_transforms;
+extern crate nalgebra as na;
+
+use coord_transforms::geo;
+use coord_transforms::structs::geo_ellipsoid;
+use na::Vector3;
+use std::time::Instant;
+
+const POINTS: usize = 1_000_000;
+
+fn main() {
+    let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                                     geo_ellipsoid::WGS84_FLATTENING);
+    let origin: Vector3<f64> = Vector3::new(48.856614f64.to_radians(), 2.352222f64.to_radians(), 1000.0);
+    let base = geo::lla2ecef(&origin, &ellipsoid);
+    let points: Vec<Vector3<f64>> = (0..POINTS)
+        .map(|i| base + Vector3::new((i % 1000) as f64, (i % 777) as f64, (i % 333) as f64))
+        .collect();
+
+    let start = Instant::now();
+    let mut sum = 0.0;
+    for p in points.iter() {
+        sum += geo::ecef2enu(p, &origin, &ellipsoid).x;
+    }
+    let free = start.elapsed();
+
+    let start = Instant::now();
+    let ltp = geo::LocalTangentPlane::new(&origin, &ellipsoid);
+    let mut cached_sum = 0.0;
+    for p in points.iter() {
+        cached_sum += ltp.to_enu(p).x;
+    }
+    let cached = start.elapsed();
+
+    let start = Instant::now();
+    let batch_sum: f64 = ltp.to_enu_batch(&points).iter().map(|v| v.x).sum();
+    let batch = start.elapsed();
+
+    assert_eq!(sum, cached_sum);
+    assert_eq!(sum, batch_sum);
+    println!("ecef2enu free function:      {:?} for {} points", free, POINTS);
+    println!("LocalTangentPlane::to_enu:   {:?} for {} points", cached, POINTS);
+    println!("LocalTangentPlane batch:     {:?} for {} points", batch, POINTS);
+}
@@ -0,0 +1,427 @@
+use na::Vector3;
+use structs::geo_ellipsoid;
+use structs::utm;
+use projections::{ProjectedCrs, TransverseMercator, LambertConformalConic, Mercator, AlbersEqualArea, Stereographic};
+use geo;
+use std::f64;
+use std::fmt;
+use std::error::Error;
+
+/// Supported EPSG codes and code ranges, as listed in CrsError messages
+const SUPPORTED_CODES: &str = "2056, 2154, 2157, 3857, 4258, 4326, 4978, 25828-25838, 27700, 32601-32660, 32701-32760";
+
+/// PROJ datum names with the PROJ name of their ellipsoid
+const PROJ_DATUMS: [(&str, &str); 8] = [
+    ("WGS84", "WGS84"),
+    ("NAD83", "GRS80"),
+    ("GGRS87", "GRS80"),
+    ("NAD27", "clrk66"),
+    ("OSGB36", "airy"),
+    ("ire65", "mod_airy"),
+    ("potsdam", "bessel"),
+    ("hermannskogel", "bessel")
+];
+
+/// Errors returned by the EPSG code registry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrsError {
+    /// The EPSG code is not in the registry
+    UnsupportedCode(u32)
+}
+
+impl fmt::Display for CrsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CrsError::UnsupportedCode(code) => write!(f, "EPSG:{} is not supported, supported codes are {}", code, SUPPORTED_CODES)
+        }
+    }
+}
+
+impl Error for CrsError {}
+
+/// Lists the EPSG codes known to the registry
+/// 
+/// # Return Value
+/// 
+/// * Vec<u32> - supported codes in ascending order
+pub fn supported_codes() -> Vec<u32> {
+    let mut codes = vec![2056, 2154, 2157, 3857, 4258, 4326, 4978];
+    codes.extend(25828..25839);
+    codes.push(27700);
+    codes.extend(32601..32661);
+    codes.extend(32701..32761);
+    codes
+}
+
+/// Converts coordinates between two coordinate reference systems given by EPSG code
+/// 
+/// # Arguments
+/// 
+/// * `from_epsg` - EPSG code of the input coordinates
+/// * `to_epsg` - EPSG code of the output coordinates
+/// * `vec` - Vector3 reference to the input coordinates
+/// 
+/// # Return Value
+/// 
+/// * Result<Vector3<f64>, CrsError> - output coordinates, or UnsupportedCode for a code outside the registry
+/// 
+/// # Formula
+/// 
+/// * vec is taken to WGS84 (lat, lon, h) and from there to the target system
+/// 
+/// Geographic systems (4326, 4258) are (latitude, longitude, height) in radians and meters like the
+/// rest of the crate, 4978 is ECEF in meters and projected systems are (easting, northing, height) in
+/// meters. ETRS89 (4258, 258xx, 2154, 2157) is treated as coincident with WGS84, which holds to
+/// better than a meter. The OSGB36 (27700) and CH1903+ (2056) datum shifts are the single Helmert
+/// and translation steps of geo::lla2osgb and geo::lla2lv95.
+pub fn convert(from_epsg: u32, to_epsg: u32, vec: &Vector3<f64>) -> Result<Vector3<f64>, CrsError> {
+    let lla_vec = to_wgs84(from_epsg, vec)?;
+    from_wgs84(to_epsg, &lla_vec)
+}
+
+/// WGS84 (lat, lon, h) of coordinates in a registered system
+fn to_wgs84(epsg: u32, vec: &Vector3<f64>) -> Result<Vector3<f64>, CrsError> {
+    match epsg {
+        4326 | 4258 => Ok(*vec),
+        4978 => Ok(geo::ecef2lla(vec, &geo_ellipsoid::GeoEllipsoid::wgs84())),
+        3857 => Ok(geo::webmercator2lla(vec)),
+        27700 => Ok(geo::osgb2lla(vec)),
+        2154 => Ok(geo::lambert932lla(vec)),
+        2157 => Ok(geo::itm2lla(vec)),
+        2056 => Ok(geo::lv952lla(vec)),
+        _ => utm_projection(epsg).map(|projection| projection.inverse(vec)).ok_or(CrsError::UnsupportedCode(epsg))
+    }
+}
+
+/// Coordinates in a registered system of WGS84 (lat, lon, h)
+fn from_wgs84(epsg: u32, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, CrsError> {
+    match epsg {
+        4326 | 4258 => Ok(*lla_vec),
+        4978 => Ok(geo::lla2ecef(lla_vec, &geo_ellipsoid::GeoEllipsoid::wgs84())),
+        3857 => Ok(geo::lla2webmercator(lla_vec)),
+        27700 => Ok(geo::lla2osgb(lla_vec)),
+        2154 => Ok(geo::lla2lambert93(lla_vec)),
+        2157 => Ok(geo::lla2itm(lla_vec)),
+        2056 => Ok(geo::lla2lv95(lla_vec)),
+        _ => utm_projection(epsg).map(|projection| projection.forward(lla_vec)).ok_or(CrsError::UnsupportedCode(epsg))
+    }
+}
+
+/// Errors returned when parsing a PROJ string
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The string has no +proj parameter
+    MissingProjection,
+    /// The projection needs a parameter the string does not give
+    MissingParameter(String),
+    /// The token is a parameter or flag the parser does not support
+    UnsupportedToken(String),
+    /// The token's value does not parse or names an unsupported projection, ellipsoid, datum or unit
+    InvalidValue(String)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::MissingProjection => write!(f, "PROJ string has no +proj parameter"),
+            ParseError::MissingParameter(ref key) => write!(f, "PROJ string is missing +{}", key),
+            ParseError::UnsupportedToken(ref token) => write!(f, "PROJ token '{}' is not supported", token),
+            ParseError::InvalidValue(ref token) => write!(f, "PROJ token '{}' has an invalid value", token)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Builds a projection from a PROJ string
+/// 
+/// # Arguments
+/// 
+/// * `proj` - PROJ string, e.g. "+proj=utm +zone=31 +datum=WGS84 +units=m +no_defs"
+/// 
+/// # Return Value
+/// 
+/// * Result<ProjectedCrs, ParseError> - the configured projection, or an error naming the offending token
+/// 
+/// # Formula
+/// 
+/// * proj=longlat, utm (zone, south), tmerc, merc (lat_ts or k_0), lcc, aea, stere (polar) and sterea (oblique)
+/// * ellps, datum, a with f or rf select the ellipsoid, GRS80 when none is given
+/// * lat_0, lon_0, lat_1, lat_2 and lat_ts in degrees, x_0 and y_0 in meters, k_0 (or k) and units=m
+/// 
+/// A datum only selects its ellipsoid, no datum shift is applied. The flags no_defs and wktext and
+/// the type=crs parameter are accepted and ignored.
+pub fn from_proj_string(proj: &str) -> Result<ProjectedCrs, ParseError> {
+    let mut parameters: Vec<(&str, Option<&str>, &str)> = Vec::new();
+    for token in proj.split_whitespace() {
+        let parameter = token.trim_start_matches('+');
+        let (key, value) = match parameter.find('=') {
+            Some(index) => (&parameter[..index], Some(&parameter[index + 1..])),
+            None => (parameter, None)
+        };
+        match (key, value) {
+            ("proj", Some(_)) | ("zone", Some(_)) | ("south", None) | ("ellps", Some(_)) | ("datum", Some(_))
+            | ("a", Some(_)) | ("f", Some(_)) | ("rf", Some(_)) | ("x_0", Some(_)) | ("y_0", Some(_))
+            | ("k_0", Some(_)) | ("k", Some(_)) | ("lat_0", Some(_)) | ("lon_0", Some(_)) | ("lat_1", Some(_))
+            | ("lat_2", Some(_)) | ("lat_ts", Some(_)) => parameters.push((key, value, token)),
+            ("units", Some("m")) | ("no_defs", None) | ("wktext", None) | ("type", Some("crs")) => {},
+            ("units", Some(_)) | ("type", Some(_)) => return Err(ParseError::InvalidValue(token.to_string())),
+            _ => return Err(ParseError::UnsupportedToken(token.to_string()))
+        }
+    }
+    let find = |key: &str| parameters.iter().rev().find(|parameter| parameter.0 == key);
+    let number = |key: &str| -> Result<Option<f64>, ParseError> {
+        match find(key) {
+            Some(&(_, Some(value), token)) => value.parse::<f64>().map(Some).map_err(|_| ParseError::InvalidValue(token.to_string())),
+            _ => Ok(None)
+        }
+    };
+    let angle = |key: &str| -> Result<Option<f64>, ParseError> { Ok(number(key)?.map(f64::to_radians)) };
+    let ellipsoid = proj_ellipsoid(find("ellps"), find("datum"), number("a")?, number("f")?, number("rf")?)?;
+    let lat0 = angle("lat_0")?.unwrap_or(0.0);
+    let lon0 = angle("lon_0")?.unwrap_or(0.0);
+    let false_easting = number("x_0")?.unwrap_or(0.0);
+    let false_northing = number("y_0")?.unwrap_or(0.0);
+    let scale_factor = match number("k_0")? {
+        Some(k0) => Some(k0),
+        None => number("k")?
+    };
+    let required = |key: &str| -> Result<f64, ParseError> { angle(key)?.ok_or_else(|| ParseError::MissingParameter(key.to_string())) };
+    let &(_, proj_name, proj_token) = find("proj").ok_or(ParseError::MissingProjection)?;
+    match proj_name.unwrap_or("") {
+        "longlat" | "latlong" | "lonlat" | "latlon" => Ok(ProjectedCrs::LongLat),
+        "utm" => {
+            let &(_, zone, zone_token) = find("zone").ok_or_else(|| ParseError::MissingParameter("zone".to_string()))?;
+            let zone = match zone.unwrap_or("").parse::<u8>() {
+                Ok(zone) if (1..=60).contains(&zone) => zone,
+                _ => return Err(ParseError::InvalidValue(zone_token.to_string()))
+            };
+            let false_northing = if find("south").is_some() { utm::UTM_FALSE_NORTHING_SOUTH_METERS } else { 0.0 };
+            Ok(ProjectedCrs::TransverseMercator(TransverseMercator::new(0.0, (zone as f64 * 6.0 - 183.0).to_radians(),
+                utm::UTM_SCALE_FACTOR, utm::UTM_FALSE_EASTING_METERS, false_northing, &ellipsoid)))
+        },
+        "tmerc" => Ok(ProjectedCrs::TransverseMercator(TransverseMercator::new(lat0, lon0, scale_factor.unwrap_or(1.0),
+            false_easting, false_northing, &ellipsoid))),
+        "merc" => {
+            let lat_ts = match (angle("lat_ts")?, scale_factor) {
+                (Some(lat_ts), _) => lat_ts,
+                //k0 = cos(lat_ts) / sqrt(1 - e^2 * sin(lat_ts)^2) solved for sin(lat_ts)^2
+                (None, Some(k0)) if k0 > 0.0 && k0 <= 1.0 => {
+                    let e2 = ellipsoid.get_first_ecc_squared();
+                    ((1.0 - k0.powi(2)) / (1.0 - k0.powi(2) * e2)).sqrt().asin()
+                },
+                (None, Some(_)) => return Err(ParseError::InvalidValue(find("k_0").or_else(|| find("k")).unwrap().2.to_string())),
+                (None, None) => 0.0
+            };
+            Ok(ProjectedCrs::Mercator(Mercator::new_with_origin(&ellipsoid, lat_ts, lon0, false_easting, false_northing)))
+        },
+        "lcc" => {
+            let sp1 = required("lat_1")?;
+            let sp2 = angle("lat_2")?.unwrap_or(sp1);
+            match scale_factor {
+                None => Ok(ProjectedCrs::LambertConformalConic(LambertConformalConic::new(lat0, lon0, sp1, sp2,
+                    false_easting, false_northing, &ellipsoid))),
+                //The scale factor form has its natural origin on the single standard parallel
+                Some(k0) if sp1 == sp2 && lat0 == sp1 =>
+                    Ok(ProjectedCrs::LambertConformalConic(LambertConformalConic::new_one_parallel(sp1, lon0, k0,
+                        false_easting, false_northing, &ellipsoid))),
+                Some(_) => Err(ParseError::UnsupportedToken(find("k_0").or_else(|| find("k")).unwrap().2.to_string()))
+            }
+        },
+        "aea" => {
+            let sp1 = required("lat_1")?;
+            let sp2 = angle("lat_2")?.unwrap_or(sp1);
+            Ok(ProjectedCrs::AlbersEqualArea(AlbersEqualArea::new(lat0, lon0, sp1, sp2, false_easting, false_northing, &ellipsoid)))
+        },
+        "stere" => {
+            let hemisphere = if (lat0 - f64::consts::FRAC_PI_2).abs() < 1e-12 {
+                utm::Hemisphere::North
+            } else if (lat0 + f64::consts::FRAC_PI_2).abs() < 1e-12 {
+                utm::Hemisphere::South
+            } else {
+                //Only the polar aspect is supported, the oblique double stereographic is proj=sterea
+                return Err(ParseError::InvalidValue(find("lat_0").map_or("lat_0=0", |parameter| parameter.2).to_string()));
+            };
+            match (angle("lat_ts")?, scale_factor) {
+                (Some(lat_ts), _) => Ok(ProjectedCrs::Stereographic(Stereographic::new_polar_true_scale(lat_ts.abs().copysign(lat0), lon0,
+                    false_easting, false_northing, &ellipsoid))),
+                (None, k0) => Ok(ProjectedCrs::Stereographic(Stereographic::new_polar(hemisphere, lon0, k0.unwrap_or(1.0),
+                    false_easting, false_northing, &ellipsoid)))
+            }
+        },
+        "sterea" => Ok(ProjectedCrs::Stereographic(Stereographic::new_oblique(lat0, lon0, scale_factor.unwrap_or(1.0),
+            false_easting, false_northing, &ellipsoid))),
+        _ => Err(ParseError::InvalidValue(proj_token.to_string()))
+    }
+}
+
+/// Ellipsoid of the ellps, datum, a, f and rf parameters of a PROJ string
+fn proj_ellipsoid(ellps: Option<&(&str, Option<&str>, &str)>, datum: Option<&(&str, Option<&str>, &str)>,
+                  a: Option<f64>, f: Option<f64>, rf: Option<f64>) -> Result<geo_ellipsoid::GeoEllipsoid, ParseError> {
+    if let Some(a) = a {
+        let inverse_flattening = match (rf, f) {
+            (Some(rf), _) => rf,
+            (None, Some(f)) if f != 0.0 => 1.0 / f,
+            _ => f64::INFINITY
+        };
+        return Ok(geo_ellipsoid::GeoEllipsoid::new(a, inverse_flattening));
+    }
+    let name = match (ellps, datum) {
+        (Some(&(_, Some(name), token)), _) => geo_ellipsoid::PROJ_ELLIPSOIDS.iter().find(|ellipsoid| ellipsoid.0 == name)
+            .map(|ellipsoid| ellipsoid.0).ok_or_else(|| ParseError::InvalidValue(token.to_string()))?,
+        (None, Some(&(_, Some(name), token))) => PROJ_DATUMS.iter().find(|datum| datum.0 == name)
+            .map(|datum| datum.1).ok_or_else(|| ParseError::InvalidValue(token.to_string()))?,
+        _ => "GRS80"
+    };
+    let &(_, a, inverse_flattening) = geo_ellipsoid::PROJ_ELLIPSOIDS.iter().find(|ellipsoid| ellipsoid.0 == name).unwrap();
+    Ok(geo_ellipsoid::GeoEllipsoid::new(a, inverse_flattening))
+}
+
+/// Transverse Mercator projection of the WGS84 (326xx, 327xx) and ETRS89 (258xx) UTM zones
+fn utm_projection(epsg: u32) -> Option<TransverseMercator> {
+    let (zone, false_northing, ellipsoid) = match epsg {
+        32601..=32660 => (epsg - 32600, 0.0, geo_ellipsoid::GeoEllipsoid::wgs84()),
+        32701..=32760 => (epsg - 32700, utm::UTM_FALSE_NORTHING_SOUTH_METERS, geo_ellipsoid::GeoEllipsoid::wgs84()),
+        25828..=25838 => (epsg - 25800, 0.0, geo_ellipsoid::GeoEllipsoid::grs80()),
+        _ => return None
+    };
+    Some(TransverseMercator::new(0.0, (zone as f64 * 6.0 - 183.0).to_radians(), utm::UTM_SCALE_FACTOR,
+                                 utm::UTM_FALSE_EASTING_METERS, false_northing, &ellipsoid))
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use projections;
+
+    #[test]
+    fn test_convert_utm() {
+        //Paris in UTM zone 31N
+        let lla_vec = Vector3::new(48.856614f64.to_radians(), 2.352222f64.to_radians(), 0.0);
+        let utm_vec = convert(4326, 32631, &lla_vec).unwrap();
+        assert_approx_eq!(utm_vec.x, 452484.1597, 1e-3);
+        assert_approx_eq!(utm_vec.y, 5411718.7194, 1e-3);
+        let back = convert(32631, 4326, &utm_vec).unwrap();
+        assert_approx_eq!(back.x, lla_vec.x, 1e-12);
+        assert_approx_eq!(back.y, lla_vec.y, 1e-12);
+        //Southern zones carry the 10000 km false northing
+        let south = convert(4326, 32731, &Vector3::new(-1.0f64.to_radians(), 3.0f64.to_radians(), 0.0)).unwrap();
+        assert_approx_eq!(south.x, 500000.0, 1e-6);
+        assert!(south.y < 10000000.0 && south.y > 9880000.0);
+        //Zone 32 of ETRS89 and WGS84 differ only by the ellipsoid flattening
+        let etrs = convert(4326, 25832, &lla_vec).unwrap();
+        let wgs = convert(4326, 32632, &lla_vec).unwrap();
+        assert_approx_eq!(etrs.x, wgs.x, 1e-3);
+        assert_approx_eq!(etrs.y, wgs.y, 1e-3);
+    }
+
+    #[test]
+    fn test_convert_webmercator() {
+        //Eiffel Tower
+        let lla_vec = Vector3::new(48.858222f64.to_radians(), 2.2945f64.to_radians(), 0.0);
+        let merc_vec = convert(4326, 3857, &lla_vec).unwrap();
+        assert_approx_eq!(merc_vec.x, 255422.57, 0.01);
+        assert_approx_eq!(merc_vec.y, 6250838.78, 0.01);
+        //Projected to projected through the geographic hub
+        let utm_vec = convert(3857, 32631, &merc_vec).unwrap();
+        let direct = convert(4326, 32631, &lla_vec).unwrap();
+        assert_approx_eq!(utm_vec.x, direct.x, 1e-6);
+        assert_approx_eq!(utm_vec.y, direct.y, 1e-6);
+    }
+
+    #[test]
+    fn test_convert_ecef() {
+        let lla_vec = Vector3::new(0.0, 0.0, 0.0);
+        let ecef_vec = convert(4326, 4978, &lla_vec).unwrap();
+        assert_approx_eq!(ecef_vec.x, geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS, 1e-6);
+        let lla_vec = Vector3::new(51.5f64.to_radians(), -0.1f64.to_radians(), 45.0);
+        let osgb_vec = convert(4978, 27700, &convert(4326, 4978, &lla_vec).unwrap()).unwrap();
+        let direct = convert(4326, 27700, &lla_vec).unwrap();
+        assert_approx_eq!(osgb_vec.x, direct.x, 1e-6);
+        assert_approx_eq!(osgb_vec.y, direct.y, 1e-6);
+    }
+
+    #[test]
+    fn test_unsupported_code() {
+        let vec = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(convert(4326, 9999, &vec), Err(CrsError::UnsupportedCode(9999)));
+        assert_eq!(convert(32600, 4326, &vec), Err(CrsError::UnsupportedCode(32600)));
+        assert!(format!("{}", CrsError::UnsupportedCode(9999)).contains("32601-32660"));
+        for code in supported_codes().iter() {
+            assert!(convert(4326, *code, &Vector3::new(0.8, 0.1, 0.0)).is_ok());
+        }
+        assert_eq!(supported_codes().len(), 139);
+    }
+
+    #[test]
+    fn test_from_proj_string() {
+        let wgs84 = geo_ellipsoid::GeoEllipsoid::wgs84();
+        let grs80 = geo_ellipsoid::GeoEllipsoid::grs80();
+        let lla_vec = Vector3::new(48.856614f64.to_radians(), 2.352222f64.to_radians(), 35.0);
+        //EPSG:32631
+        let utm = from_proj_string("+proj=utm +zone=31 +datum=WGS84 +units=m +no_defs").unwrap();
+        assert_eq!(utm.forward(&lla_vec), convert(4326, 32631, &lla_vec).unwrap());
+        let utm_south = from_proj_string("+proj=utm +zone=33 +south +ellps=GRS80 +units=m +no_defs").unwrap();
+        assert_eq!(utm_south, ProjectedCrs::TransverseMercator(TransverseMercator::new(0.0, 15.0f64.to_radians(), 0.9996,
+                                                                                     500000.0, 10000000.0, &grs80)));
+        //EPSG:27700 without the datum shift
+        let osgb = from_proj_string("+proj=tmerc +lat_0=49 +lon_0=-2 +k=0.9996012717 +x_0=400000 +y_0=-100000 +ellps=airy +units=m +no_defs").unwrap();
+        let airy = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::AIRY_SEMI_MAJOR_AXIS_METERS, geo_ellipsoid::AIRY_FLATTENING);
+        let direct = TransverseMercator::new(49.0f64.to_radians(), -2.0f64.to_radians(), 0.9996012717, 400000.0, -100000.0, &airy);
+        assert_eq!(osgb.forward(&lla_vec), direct.forward(&lla_vec));
+        //EPSG:2154
+        let lambert = from_proj_string("+proj=lcc +lat_0=46.5 +lon_0=3 +lat_1=49 +lat_2=44 +x_0=700000 +y_0=6600000 +ellps=GRS80 +units=m +no_defs").unwrap();
+        let (proj_vec, direct) = (lambert.forward(&lla_vec), projections::lambert93().forward(&lla_vec));
+        assert_approx_eq!(proj_vec.x, direct.x, 1e-6);
+        assert_approx_eq!(proj_vec.y, direct.y, 1e-6);
+        //EPSG:3395
+        let mercator = from_proj_string("+proj=merc +lon_0=0 +k=1 +x_0=0 +y_0=0 +datum=WGS84 +units=m +no_defs").unwrap();
+        assert_eq!(mercator.forward(&lla_vec), Mercator::new(&wgs84, 0.0).forward(&lla_vec));
+        //A scale factor selects the latitude of true scale with that scale on the equator
+        let lat_ts = 37.0f64.to_radians();
+        let k0 = lat_ts.cos() / (1.0 - (wgs84.get_first_ecc() * lat_ts.sin()).powi(2)).sqrt();
+        let scaled = from_proj_string(&format!("+proj=merc +k_0={} +ellps=WGS84", k0)).unwrap();
+        let (proj_vec, direct) = (scaled.forward(&lla_vec), Mercator::new(&wgs84, lat_ts).forward(&lla_vec));
+        assert_approx_eq!(proj_vec.x, direct.x, 1e-6);
+        assert_approx_eq!(proj_vec.y, direct.y, 1e-6);
+        assert_eq!(from_proj_string("+proj=merc +lat_ts=37 +ellps=WGS84").unwrap().forward(&lla_vec), direct);
+        //EPSG:5070
+        let conus = from_proj_string("+proj=aea +lat_0=23 +lon_0=-96 +lat_1=29.5 +lat_2=45.5 +x_0=0 +y_0=0 +datum=NAD83 +units=m +no_defs").unwrap();
+        let direct = AlbersEqualArea::new(23.0f64.to_radians(), -96.0f64.to_radians(), 29.5f64.to_radians(), 45.5f64.to_radians(), 0.0, 0.0, &grs80);
+        assert_eq!(conus.forward(&lla_vec), direct.forward(&lla_vec));
+        //EPSG:3413
+        let arctic = from_proj_string("+proj=stere +lat_0=90 +lat_ts=70 +lon_0=-45 +k=1 +x_0=0 +y_0=0 +datum=WGS84 +units=m +no_defs").unwrap();
+        let direct = Stereographic::new_polar_true_scale(70.0f64.to_radians(), -45.0f64.to_radians(), 0.0, 0.0, &wgs84);
+        assert_eq!(arctic.forward(&lla_vec), direct.forward(&lla_vec));
+        //EPSG:28992
+        let rd = from_proj_string("+proj=sterea +lat_0=52.1561605555556 +lon_0=5.38763888888889 +k=0.9999079 +x_0=155000 +y_0=463000 +ellps=bessel +units=m +no_defs").unwrap();
+        let bessel = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::BESSEL_1841_SEMI_MAJOR_AXIS_METERS, geo_ellipsoid::BESSEL_1841_FLATTENING);
+        let direct = Stereographic::new_oblique(52.1561605555556f64.to_radians(), 5.38763888888889f64.to_radians(), 0.9999079,
+                                                155000.0, 463000.0, &bessel);
+        assert_eq!(rd.forward(&lla_vec), direct.forward(&lla_vec));
+        //Geographic and an explicit sphere
+        let longlat = from_proj_string("+proj=longlat +datum=WGS84 +no_defs +type=crs").unwrap();
+        assert_eq!(longlat, ProjectedCrs::LongLat);
+        assert_eq!(longlat.inverse(&longlat.forward(&lla_vec)), lla_vec);
+        let sphere = from_proj_string("proj=tmerc a=6371000").unwrap();
+        let direct = TransverseMercator::new(0.0, 0.0, 1.0, 0.0, 0.0, &geo_ellipsoid::GeoEllipsoid::new(6371000.0, f64::INFINITY));
+        assert_eq!(sphere.forward(&lla_vec), direct.forward(&lla_vec));
+    }
+
+    #[test]
+    fn test_from_proj_string_errors() {
+        assert_eq!(from_proj_string("+proj=lcc +lat_0=46.5 +lat_1=49 +lat_2=44 +ellps=GRS80 +towgs84=0,0,0,0,0,0,0 +units=m"),
+                   Err(ParseError::UnsupportedToken("+towgs84=0,0,0,0,0,0,0".to_string())));
+        assert_eq!(from_proj_string("+proj=utm +zone=31 +units=us-ft"), Err(ParseError::InvalidValue("+units=us-ft".to_string())));
+        assert_eq!(from_proj_string("+proj=robin +lon_0=0"), Err(ParseError::InvalidValue("+proj=robin".to_string())));
+        assert_eq!(from_proj_string("+proj=utm +zone=61"), Err(ParseError::InvalidValue("+zone=61".to_string())));
+        assert_eq!(from_proj_string("+proj=utm +datum=WGS84"), Err(ParseError::MissingParameter("zone".to_string())));
+        assert_eq!(from_proj_string("+proj=aea +lat_2=45.5"), Err(ParseError::MissingParameter("lat_1".to_string())));
+        assert_eq!(from_proj_string("+proj=tmerc +lat_0=north"), Err(ParseError::InvalidValue("+lat_0=north".to_string())));
+        assert_eq!(from_proj_string("+proj=tmerc +ellps=sphere"), Err(ParseError::InvalidValue("+ellps=sphere".to_string())));
+        assert_eq!(from_proj_string("+proj=stere +lat_0=52"), Err(ParseError::InvalidValue("+lat_0=52".to_string())));
+        assert_eq!(from_proj_string("+ellps=WGS84 +units=m"), Err(ParseError::MissingProjection));
+        assert!(format!("{}", ParseError::UnsupportedToken("+towgs84=1,2,3".to_string())).contains("+towgs84=1,2,3"));
+    }
+}
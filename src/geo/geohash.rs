@@ -0,0 +1,179 @@
+use std::f64;
+use geo::{GeoError, wrap_longitude_degrees};
+
+/// Maximum supported geohash length (characters)
+pub const GEOHASH_MAX_PRECISION: usize = 12;
+
+/// Geohash base-32 alphabet
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Encodes a position as a geohash
+/// 
+/// # Arguments
+/// 
+/// * `lat` - latitude (radians)
+/// * `lon` - longitude (radians)
+/// * `precision` - number of characters, clamped to 1 - GEOHASH_MAX_PRECISION
+/// 
+/// # Return Value
+/// 
+/// * String - geohash
+/// 
+/// # Formula
+/// 
+/// * Bits alternately bisect the longitude and latitude intervals, starting with longitude
+/// * Every 5 bits form one base-32 character
+pub fn encode(lat: f64, lon: f64, precision: usize) -> String {
+    let precision = precision.clamp(1, GEOHASH_MAX_PRECISION);
+    let lat = lat.to_degrees().clamp(-90.0, 90.0);
+    let lon = wrap_longitude_degrees(lon);
+    let mut lat_range = (-90.0, 90.0);
+    let mut lon_range = (-180.0, 180.0);
+    let mut hash = String::with_capacity(precision);
+    let mut even_bit = true;
+    for _ in 0..precision {
+        let mut index = 0;
+        for _ in 0..5 {
+            let (value, range) = if even_bit { (lon, &mut lon_range) } else { (lat, &mut lat_range) };
+            let mid = (range.0 + range.1) / 2.0;
+            index <<= 1;
+            if value >= mid {
+                index |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even_bit = !even_bit;
+        }
+        hash.push(GEOHASH_ALPHABET[index] as char);
+    }
+    hash
+}
+
+/// Decodes a geohash to the center of its cell
+/// 
+/// # Arguments
+/// 
+/// * `hash` - geohash, case insensitive
+/// 
+/// # Return Value
+/// 
+/// * Result<(f64, f64), GeoError> - lat, lon (radians), or InvalidGridReference for an empty hash or a character outside the alphabet
+pub fn decode(hash: &str) -> Result<(f64, f64), GeoError> {
+    let (lat_min, lon_min, lat_max, lon_max) = bbox(hash)?;
+    Ok(((lat_min + lat_max) / 2.0, (lon_min + lon_max) / 2.0))
+}
+
+/// Decodes a geohash to the bounds of its cell
+/// 
+/// # Arguments
+/// 
+/// * `hash` - geohash, case insensitive
+/// 
+/// # Return Value
+/// 
+/// * Result<(f64, f64, f64, f64), GeoError> - lat_min, lon_min, lat_max, lon_max (radians), or InvalidGridReference for an empty hash or a character outside the alphabet
+pub fn bbox(hash: &str) -> Result<(f64, f64, f64, f64), GeoError> {
+    if hash.is_empty() {
+        return Err(GeoError::InvalidGridReference);
+    }
+    let mut lat_range = (-90.0f64, 90.0f64);
+    let mut lon_range = (-180.0f64, 180.0f64);
+    let mut even_bit = true;
+    for c in hash.bytes() {
+        let index = GEOHASH_ALPHABET.iter().position(|&x| x == c.to_ascii_lowercase())
+            .ok_or(GeoError::InvalidGridReference)?;
+        for bit in (0..5).rev() {
+            let range = if even_bit { &mut lon_range } else { &mut lat_range };
+            let mid = (range.0 + range.1) / 2.0;
+            if (index >> bit) & 1 == 1 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even_bit = !even_bit;
+        }
+    }
+    Ok((lat_range.0.to_radians(), lon_range.0.to_radians(), lat_range.1.to_radians(), lon_range.1.to_radians()))
+}
+
+/// Finds the 8 geohashes adjacent to a geohash
+/// 
+/// # Arguments
+/// 
+/// * `hash` - geohash, case insensitive
+/// 
+/// # Return Value
+/// 
+/// * Result<[String; 8], GeoError> - neighbors in the order N, NE, E, SE, S, SW, W, NW, or InvalidGridReference for a malformed hash
+/// 
+/// Neighbors wrap across the antimeridian. Past a pole there is no cell, so the row of the hash itself is used instead.
+pub fn neighbors(hash: &str) -> Result<[String; 8], GeoError> {
+    let (lat_min, lon_min, lat_max, lon_max) = bbox(hash)?;
+    let lat = (lat_min + lat_max) / 2.0;
+    let lon = (lon_min + lon_max) / 2.0;
+    let dlat = lat_max - lat_min;
+    let dlon = lon_max - lon_min;
+    let neighbor = |rows: f64, cols: f64| {
+        let mut neighbor_lat = lat + rows * dlat;
+        if neighbor_lat.abs() > f64::consts::FRAC_PI_2 {
+            neighbor_lat = lat;
+        }
+        encode(neighbor_lat, lon + cols * dlon, hash.len())
+    };
+    Ok([neighbor(1.0, 0.0), neighbor(1.0, 1.0), neighbor(0.0, 1.0), neighbor(-1.0, 1.0),
+        neighbor(-1.0, 0.0), neighbor(-1.0, -1.0), neighbor(0.0, -1.0), neighbor(1.0, -1.0)])
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(42.605f64.to_radians(), -5.603f64.to_radians(), 5), "ezs42");
+        assert_eq!(encode(57.64911f64.to_radians(), 10.40744f64.to_radians(), 11), "u4pruydqqvj");
+        assert_eq!(encode(0.0, 0.0, 12), "s00000000000");
+        //Precision is clamped to the supported range
+        assert_eq!(encode(0.0, 0.0, 0), "s");
+        assert_eq!(encode(0.0, 0.0, 20).len(), GEOHASH_MAX_PRECISION);
+        //180 degrees east is the antimeridian cell on the western edge
+        assert_eq!(encode(0.0, f64::consts::PI, 1), encode(0.0, -f64::consts::PI, 1));
+    }
+    #[test]
+    fn test_decode() {
+        let (lat, lon) = decode("ezs42").unwrap();
+        assert_approx_eq!(lat.to_degrees(), 42.605, 0.03);
+        assert_approx_eq!(lon.to_degrees(), -5.603, 0.03);
+        assert_eq!(decode("EZS42"), decode("ezs42"));
+        let (lat_min, lon_min, lat_max, lon_max) = bbox("ezs42").unwrap();
+        assert_approx_eq!(lat_min.to_degrees(), 42.583_007_812_5, 1e-9);
+        assert_approx_eq!(lat_max.to_degrees(), 42.626_953_125, 1e-9);
+        assert_approx_eq!(lon_min.to_degrees(), -5.625, 1e-9);
+        assert_approx_eq!(lon_max.to_degrees(), -5.581_054_687_5, 1e-9);
+        //Invalid characters are rejected
+        assert_eq!(decode("ezs4a"), Err(GeoError::InvalidGridReference));
+        assert_eq!(decode("ezs4i"), Err(GeoError::InvalidGridReference));
+        assert_eq!(decode(""), Err(GeoError::InvalidGridReference));
+        assert_eq!(bbox("é"), Err(GeoError::InvalidGridReference));
+        //Encoding a cell center at full precision restores the hash
+        for hash in ["ezs42", "u4pruydqqvj", "s00000000000", "zzzzzzzzzzzz", "0"].iter() {
+            let (lat, lon) = decode(hash).unwrap();
+            assert_eq!(encode(lat, lon, hash.len()), *hash);
+        }
+    }
+    #[test]
+    fn test_neighbors() {
+        assert_eq!(neighbors("ezs42").unwrap(), ["ezs48", "ezs49", "ezs43", "ezs41", "ezs40", "ezefp", "ezefr", "ezefx"]);
+        //Cells on the equator have neighbors in the other hemisphere
+        assert_eq!(neighbors("s").unwrap(), ["u", "v", "t", "m", "k", "7", "e", "g"]);
+        //Cells on the antimeridian wrap around
+        assert_eq!(neighbors("8").unwrap(), ["b", "c", "9", "3", "2", "r", "x", "z"]);
+        assert_eq!(neighbors("x").unwrap()[2], "8");
+        //Past the pole the row of the cell itself is used
+        let north = neighbors("b").unwrap();
+        assert_eq!(north[0], "b");
+        assert_eq!(north[1], "c");
+        assert_eq!(neighbors("ezs4!"), Err(GeoError::InvalidGridReference));
+    }
+}
@@ -0,0 +1,882 @@
+use na::Vector3;
+use std::f64;
+use structs::geo_ellipsoid;
+use super::{wrap_azimuth, wrap_longitude};
+
+/// Solution of the inverse geodesic problem by Karney's method
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KarneyInverse {
+    /// Geodesic distance (meters)
+    pub distance: f64,
+    /// Azimuth at the first point, clockwise from north in [0, 2 * pi) (radians)
+    pub initial_azimuth: f64,
+    /// Forward azimuth at the second point, clockwise from north in [0, 2 * pi) (radians)
+    pub final_azimuth: f64,
+    /// Arc length on the auxiliary sphere (radians)
+    pub arc_length: f64,
+    /// Reduced length m12 (meters)
+    pub reduced_length: f64,
+    /// Geodesic scale M12 of the second point relative to the first
+    pub geodesic_scale_12: f64,
+    /// Geodesic scale M21 of the first point relative to the second
+    pub geodesic_scale_21: f64
+}
+
+/// Solution of the direct geodesic problem by Karney's method
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KarneyDirect {
+    /// Destination latitude, longitude in (-pi, pi] and altitude (radians, radians, meters)
+    pub destination: Vector3<f64>,
+    /// Forward azimuth at the destination, clockwise from north in [0, 2 * pi) (radians)
+    pub final_azimuth: f64,
+    /// Arc length on the auxiliary sphere (radians)
+    pub arc_length: f64,
+    /// Reduced length m12 (meters)
+    pub reduced_length: f64,
+    /// Geodesic scale M12 of the destination relative to the start
+    pub geodesic_scale_12: f64,
+    /// Geodesic scale M21 of the start relative to the destination
+    pub geodesic_scale_21: f64
+}
+
+/// Computes the geodesic between two points on the ellipsoid by Karney's method
+///
+/// # Arguments
+///
+/// * `lla1` - Vector3 reference to the first LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+/// * `lla2` - Vector3 reference to the second LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+///
+/// # Return Value
+///
+/// * KarneyInverse - distance, azimuths, arc length, reduced length and geodesic scales
+///
+/// # Formula
+///
+/// * C. F. F. Karney, Algorithms for geodesics, J. Geodesy 87, 43-55 (2013)
+/// * series in the third flattening and eps to sixth order, Newton's method on the azimuth at the
+///   first point with a starting guess from the astroid for nearly antipodal points
+///
+/// Converges for every pair of points, with errors of about 15 nanometers on WGS84. Altitudes are
+/// ignored.
+pub fn geodesic_inverse(lla1: &Vector3<f64>, lla2: &Vector3<f64>, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> KarneyInverse {
+    Geodesic::new(ellipsoid).inverse(lla1.x.to_degrees(), lla1.y.to_degrees(), lla2.x.to_degrees(), lla2.y.to_degrees())
+}
+
+/// Computes the destination of a geodesic on the ellipsoid by Karney's method
+///
+/// # Arguments
+///
+/// * `lla_start` - Vector3 reference to the start LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+/// * `azimuth1` - initial azimuth, clockwise from north (radians)
+/// * `distance` - geodesic distance, negative to travel backwards (meters)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+///
+/// # Return Value
+///
+/// * KarneyDirect - destination (with the start altitude), forward azimuth there, arc length,
+///   reduced length and geodesic scales
+///
+/// # Formula
+///
+/// * C. F. F. Karney, Algorithms for geodesics, J. Geodesy 87, 43-55 (2013)
+/// * sigma12 from the distance by the reverted series, then the destination in closed form
+pub fn geodesic_direct(lla_start: &Vector3<f64>, azimuth1: f64, distance: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> KarneyDirect {
+    let mut direct = Geodesic::new(ellipsoid).direct(lla_start.x.to_degrees(), lla_start.y.to_degrees(), azimuth1.to_degrees(), distance);
+    direct.destination.z = lla_start.z;
+    direct
+}
+
+/// Computes the geodesic between two points on WGS84 by Karney's method
+///
+/// # Arguments
+///
+/// * `lla1` - Vector3 reference to the first LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+/// * `lla2` - Vector3 reference to the second LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+///
+/// # Return Value
+///
+/// * KarneyInverse - geodesic_inverse(lla1, lla2, GeoEllipsoid::default())
+pub fn geodesic_inverse_wgs84(lla1: &Vector3<f64>, lla2: &Vector3<f64>) -> KarneyInverse {
+    geodesic_inverse(lla1, lla2, &geo_ellipsoid::GeoEllipsoid::default())
+}
+
+/// Computes the destination of a geodesic on WGS84 by Karney's method
+///
+/// # Arguments
+///
+/// * `lla_start` - Vector3 reference to the start LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+/// * `azimuth1` - initial azimuth, clockwise from north (radians)
+/// * `distance` - geodesic distance, negative to travel backwards (meters)
+///
+/// # Return Value
+///
+/// * KarneyDirect - geodesic_direct(lla_start, azimuth1, distance, GeoEllipsoid::default())
+pub fn geodesic_direct_wgs84(lla_start: &Vector3<f64>, azimuth1: f64, distance: f64) -> KarneyDirect {
+    geodesic_direct(lla_start, azimuth1, distance, &geo_ellipsoid::GeoEllipsoid::default())
+}
+
+/// Order of the series expansions
+const ORDER: usize = 6;
+
+/// sqrt(f64::MIN_POSITIVE), the square root of the smallest positive normal double, guarding divisions by cos(beta)
+const TINY: f64 = 1.4916681462400413e-154;
+
+const TOL0: f64 = f64::EPSILON;
+const TOL1: f64 = 200.0 * TOL0;
+const MAXIT1: usize = 20;
+const MAXIT2: usize = MAXIT1 + 53 + 10;
+
+/// Ellipsoid constants and series coefficients of Karney's method
+struct Geodesic {
+    a: f64,
+    f: f64,
+    f1: f64,
+    ep2: f64,
+    n: f64,
+    b: f64,
+    etol2: f64,
+    a3x: [f64; ORDER],
+    c3x: [f64; 15]
+}
+
+/// Auxiliary sphere quantities of a geodesic between two points
+struct Lengths {
+    s12b: f64,
+    m12b: f64,
+    m0: f64,
+    big_m12: f64,
+    big_m21: f64
+}
+
+impl Geodesic {
+    fn new(ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> Geodesic {
+        let a = ellipsoid.get_semi_major_axis();
+        let f = ellipsoid.get_flattening();
+        let f1 = 1.0 - f;
+        let e2 = f * (2.0 - f);
+        let n = f / (2.0 - f);
+        let tol2 = TOL0.sqrt();
+        let mut geodesic = Geodesic {
+            a,
+            f,
+            f1,
+            ep2: e2 / f1.powi(2),
+            n,
+            b: a * f1,
+            etol2: 0.1 * tol2 / ((f.abs().max(0.001) * (1.0 - f / 2.0).min(1.0) / 2.0).sqrt()),
+            a3x: [0.0; ORDER],
+            c3x: [0.0; 15]
+        };
+        geodesic.a3_coefficients();
+        geodesic.c3_coefficients();
+        geodesic
+    }
+
+    fn a3_coefficients(&mut self) {
+        const COEFF: [f64; 18] = [-3.0, 128.0, -2.0, -3.0, 64.0, -1.0, -3.0, -1.0, 16.0, 3.0, -1.0, -2.0, 8.0, 1.0, -1.0, 2.0, 1.0, 1.0];
+        let mut o = 0;
+        for (k, j) in (0..ORDER).rev().enumerate() {
+            let m = (ORDER - j - 1).min(j);
+            self.a3x[k] = polyval(m, &COEFF[o..], self.n) / COEFF[o + m + 1];
+            o += m + 2;
+        }
+    }
+
+    fn c3_coefficients(&mut self) {
+        const COEFF: [f64; 45] = [3.0, 128.0, 2.0, 5.0, 128.0, -1.0, 3.0, 3.0, 64.0, -1.0, 0.0, 1.0, 8.0, -1.0, 1.0, 4.0,
+                                  5.0, 256.0, 1.0, 3.0, 128.0, -3.0, -2.0, 3.0, 64.0, 1.0, -3.0, 2.0, 32.0,
+                                  7.0, 512.0, -10.0, 9.0, 384.0, 5.0, -9.0, 5.0, 192.0,
+                                  7.0, 512.0, -14.0, 7.0, 512.0,
+                                  21.0, 2560.0];
+        let (mut o, mut k) = (0, 0);
+        for l in 1..ORDER {
+            for j in (l..ORDER).rev() {
+                let m = (ORDER - j - 1).min(j);
+                self.c3x[k] = polyval(m, &COEFF[o..], self.n) / COEFF[o + m + 1];
+                k += 1;
+                o += m + 2;
+            }
+        }
+    }
+
+    fn a3f(&self, eps: f64) -> f64 {
+        polyval(ORDER - 1, &self.a3x, eps)
+    }
+
+    fn c3f(&self, eps: f64) -> [f64; ORDER] {
+        let mut c = [0.0; ORDER];
+        let (mut mult, mut o) = (1.0, 0);
+        for (l, c_l) in c.iter_mut().enumerate().skip(1) {
+            let m = ORDER - l - 1;
+            mult *= eps;
+            *c_l = mult * polyval(m, &self.c3x[o..], eps);
+            o += m + 1;
+        }
+        c
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn lengths(&self, eps: f64, sig12: f64, ssig1: f64, csig1: f64, dn1: f64, ssig2: f64, csig2: f64, dn2: f64,
+               cbet1: f64, cbet2: f64) -> Lengths {
+        let c1a = c1f(eps);
+        let c2a = c2f(eps);
+        let a1 = 1.0 + a1m1f(eps);
+        let a2 = 1.0 + a2m1f(eps);
+        let m0 = a1 - a2;
+        let b1 = sin_cos_series(ssig2, csig2, &c1a) - sin_cos_series(ssig1, csig1, &c1a);
+        let b2 = sin_cos_series(ssig2, csig2, &c2a) - sin_cos_series(ssig1, csig1, &c2a);
+        let j12 = m0 * sig12 + (a1 * b1 - a2 * b2);
+        let csig12 = csig1 * csig2 + ssig1 * ssig2;
+        let t = self.ep2 * (cbet1 - cbet2) * (cbet1 + cbet2) / (dn1 + dn2);
+        Lengths {
+            s12b: a1 * (sig12 + b1),
+            m12b: dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12,
+            m0,
+            big_m12: csig12 + (t * ssig2 - csig2 * j12) * ssig1 / dn1,
+            big_m21: csig12 - (t * ssig1 - csig1 * j12) * ssig2 / dn2
+        }
+    }
+
+    /// Starting azimuth of the Newton iteration, with the solution itself for short lines
+    #[allow(clippy::too_many_arguments)]
+    fn inverse_start(&self, sbet1: f64, cbet1: f64, sbet2: f64, cbet2: f64, lam12: f64,
+                     slam12: f64, clam12: f64) -> (f64, f64, f64, f64, f64, f64) {
+        let mut sig12 = -1.0;
+        let (mut salp2, mut calp2, mut dnm) = (f64::NAN, f64::NAN, f64::NAN);
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+        let shortline = cbet12 >= 0.0 && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+        let (mut somg12, mut comg12) = if shortline {
+            let sbetm2 = (sbet1 + sbet2).powi(2);
+            let sbetm2 = sbetm2 / (sbetm2 + (cbet1 + cbet2).powi(2));
+            dnm = (1.0 + self.ep2 * sbetm2).sqrt();
+            (lam12 / (self.f1 * dnm)).sin_cos()
+        } else {
+            (slam12, clam12)
+        };
+        let mut salp1 = cbet2 * somg12;
+        let mut calp1 = if comg12 >= 0.0 {
+            sbet12 + cbet2 * sbet1 * somg12.powi(2) / (1.0 + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * somg12.powi(2) / (1.0 - comg12)
+        };
+        let ssig12 = salp1.hypot(calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+        if shortline && ssig12 < self.etol2 {
+            salp2 = cbet1 * somg12;
+            calp2 = sbet12 - cbet1 * sbet2 * if comg12 >= 0.0 { somg12.powi(2) / (1.0 + comg12) } else { 1.0 - comg12 };
+            let (s, c) = norm(salp2, calp2);
+            salp2 = s;
+            calp2 = c;
+            sig12 = ssig12.atan2(csig12);
+        } else if self.n.abs() >= 0.1 || csig12 >= 0.0 || ssig12 >= 6.0 * self.n.abs() * f64::consts::PI * cbet1.powi(2) {
+            //Not nearly antipodal, the spherical estimate is good enough
+        } else {
+            let lam12x = (-slam12).atan2(-clam12);
+            let k2 = sbet1.powi(2) * self.ep2;
+            let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+            let lamscale = self.f * cbet1 * self.a3f(eps) * f64::consts::PI;
+            let betscale = lamscale * cbet1;
+            let x = lam12x / lamscale;
+            let y = sbet12a / betscale;
+            if y > -TOL1 && x > -1.0 - 1000.0 * TOL0.sqrt() {
+                salp1 = (-x).min(1.0);
+                calp1 = -(1.0 - salp1.powi(2)).sqrt();
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale * (-x * k / (1.0 + k));
+                let (s, c) = omg12a.sin_cos();
+                somg12 = s;
+                comg12 = -c;
+                salp1 = cbet2 * somg12;
+                calp1 = sbet12a - cbet2 * sbet1 * somg12.powi(2) / (1.0 - comg12);
+            }
+        }
+        if salp1 > 0.0 {
+            let (s, c) = norm(salp1, calp1);
+            salp1 = s;
+            calp1 = c;
+        } else {
+            salp1 = 1.0;
+            calp1 = 0.0;
+        }
+        (sig12, salp1, calp1, salp2, calp2, dnm)
+    }
+
+    /// Longitude difference lambda12 for an azimuth at the first point, with its derivative
+    #[allow(clippy::too_many_arguments)]
+    fn lambda12(&self, sbet1: f64, cbet1: f64, dn1: f64, sbet2: f64, cbet2: f64, dn2: f64, salp1: f64, calp1: f64,
+                slam120: f64, clam120: f64, diffp: bool) -> [f64; 10] {
+        let calp1 = if sbet1 == 0.0 && calp1 == 0.0 { -TINY } else { calp1 };
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm(sbet1, comg1);
+        let salp2 = if cbet2 != cbet1 { salp0 / cbet2 } else { salp1 };
+        let calp2 = if cbet2 != cbet1 || sbet2.abs() != -sbet1 {
+            ((calp1 * cbet1).powi(2) + if cbet1 < -sbet1 { (cbet2 - cbet1) * (cbet1 + cbet2) } else { (sbet1 - sbet2) * (sbet1 + sbet2) }).sqrt() / cbet2
+        } else {
+            calp1.abs()
+        };
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm(sbet2, comg2);
+        let sig12 = positive_part(csig1 * ssig2 - ssig1 * csig2).atan2(csig1 * csig2 + ssig1 * ssig2);
+        let somg12 = positive_part(comg1 * somg2 - somg1 * comg2);
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+        let eta = (somg12 * clam120 - comg12 * slam120).atan2(comg12 * clam120 + somg12 * slam120);
+        let k2 = calp0.powi(2) * self.ep2;
+        let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+        let c3a = self.c3f(eps);
+        let b312 = sin_cos_series(ssig2, csig2, &c3a) - sin_cos_series(ssig1, csig1, &c3a);
+        let domg12 = -self.f * self.a3f(eps) * salp0 * (sig12 + b312);
+        let lam12 = eta + domg12;
+        let dlam12 = if !diffp {
+            f64::NAN
+        } else if calp2 == 0.0 {
+            -2.0 * self.f1 * dn1 / sbet1
+        } else {
+            self.lengths(eps, sig12, ssig1, csig1, dn1, ssig2, csig2, dn2, cbet1, cbet2).m12b * self.f1 / (calp2 * cbet2)
+        };
+        [lam12, salp2, calp2, sig12, ssig1, csig1, ssig2, csig2, eps, dlam12]
+    }
+
+    fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> KarneyInverse {
+        let lon12 = ang_diff(lon1, lon2);
+        let mut lonsign = if lon12 < 0.0 { -1.0 } else { 1.0 };
+        let lon12 = ang_round(lon12.abs());
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > 90.0 {
+            let (s, c) = sin_cos_degrees(180.0 - lon12);
+            (s, -c)
+        } else {
+            sin_cos_degrees(lon12)
+        };
+        let (mut lat1, mut lat2) = (ang_round(lat_fix(lat1)), ang_round(lat_fix(lat2)));
+        let swapp = if lat1.abs() < lat2.abs() { -1.0 } else { 1.0 };
+        if swapp < 0.0 {
+            lonsign = -lonsign;
+            ::std::mem::swap(&mut lat1, &mut lat2);
+        }
+        let latsign = if lat1 < 0.0 { 1.0 } else { -1.0 };
+        lat1 *= latsign;
+        lat2 *= latsign;
+        let (sbet1, cbet1) = sin_cos_degrees(lat1);
+        let (sbet1, cbet1) = norm(self.f1 * sbet1, cbet1);
+        let cbet1 = cbet1.max(TINY);
+        let (sbet2, cbet2) = sin_cos_degrees(lat2);
+        let (mut sbet2, mut cbet2) = norm(self.f1 * sbet2, cbet2);
+        cbet2 = cbet2.max(TINY);
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = sbet1.abs().copysign(sbet2);
+            }
+        } else if sbet2.abs() == -sbet1 {
+            cbet2 = cbet1;
+        }
+        let dn1 = (1.0 + self.ep2 * sbet1.powi(2)).sqrt();
+        let dn2 = (1.0 + self.ep2 * sbet2.powi(2)).sqrt();
+        let (mut salp1, mut calp1, mut salp2, mut calp2) = (0.0, 0.0, 0.0, 0.0);
+        let (mut sig12, mut s12x, mut m12x, mut big_m12, mut big_m21) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let mut meridian = lat1 == -90.0 || slam12 == 0.0;
+        if meridian {
+            calp1 = clam12;
+            salp1 = slam12;
+            calp2 = 1.0;
+            salp2 = 0.0;
+            let (ssig1, csig1) = (sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = (sbet2, calp2 * cbet2);
+            sig12 = positive_part(csig1 * ssig2 - ssig1 * csig2).atan2(csig1 * csig2 + ssig1 * ssig2);
+            let lengths = self.lengths(self.n, sig12, ssig1, csig1, dn1, ssig2, csig2, dn2, cbet1, cbet2);
+            s12x = lengths.s12b;
+            m12x = lengths.m12b;
+            big_m12 = lengths.big_m12;
+            big_m21 = lengths.big_m21;
+            if sig12 < 1.0 || m12x >= 0.0 {
+                if sig12 < 3.0 * TINY || (sig12 < TOL0 && (s12x < 0.0 || m12x < 0.0)) {
+                    sig12 = 0.0;
+                    m12x = 0.0;
+                    s12x = 0.0;
+                }
+                m12x *= self.b;
+                s12x *= self.b;
+            } else {
+                //The meridian is not the shortest path, past the pole on a prolate ellipsoid
+                meridian = false;
+            }
+        }
+        if !meridian && sbet1 == 0.0 && (self.f <= 0.0 || 180.0 - lon12 >= self.f * 180.0) {
+            //Along the equator
+            calp1 = 0.0;
+            calp2 = 0.0;
+            salp1 = 1.0;
+            salp2 = 1.0;
+            s12x = self.a * lam12;
+            sig12 = lam12 / self.f1;
+            m12x = self.b * sig12.sin();
+            big_m12 = sig12.cos();
+            big_m21 = big_m12;
+        } else if !meridian {
+            let (sig12_start, salp1_start, calp1_start, salp2_start, calp2_start, dnm) =
+                self.inverse_start(sbet1, cbet1, sbet2, cbet2, lam12, slam12, clam12);
+            sig12 = sig12_start;
+            salp1 = salp1_start;
+            calp1 = calp1_start;
+            if sig12 >= 0.0 {
+                //Short line solved in closed form
+                salp2 = salp2_start;
+                calp2 = calp2_start;
+                s12x = sig12 * self.b * dnm;
+                m12x = dnm.powi(2) * self.b * (sig12 / dnm).sin();
+                big_m12 = (sig12 / dnm).cos();
+                big_m21 = big_m12;
+            } else {
+                let mut numit = 0;
+                let (mut tripn, mut tripb) = (false, false);
+                let (mut salp1a, mut calp1a) = (TINY, 1.0);
+                let (mut salp1b, mut calp1b) = (TINY, -1.0);
+                let mut state;
+                loop {
+                    state = self.lambda12(sbet1, cbet1, dn1, sbet2, cbet2, dn2, salp1, calp1, slam12, clam12, numit < MAXIT1);
+                    let (v, dv) = (state[0], state[9]);
+                    if tripb || numit >= MAXIT2 || v.abs() < if tripn { 8.0 } else { 1.0 } * TOL0 {
+                        break;
+                    }
+                    if v > 0.0 && (numit > MAXIT1 || calp1 / salp1 > calp1b / salp1b) {
+                        salp1b = salp1;
+                        calp1b = calp1;
+                    } else if v < 0.0 && (numit > MAXIT1 || calp1 / salp1 < calp1a / salp1a) {
+                        salp1a = salp1;
+                        calp1a = calp1;
+                    }
+                    numit += 1;
+                    if numit < MAXIT1 && dv > 0.0 {
+                        let dalp1 = -v / dv;
+                        if dalp1.abs() < f64::consts::PI {
+                            let (sdalp1, cdalp1) = dalp1.sin_cos();
+                            let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                            if nsalp1 > 0.0 {
+                                let (s, c) = norm(nsalp1, calp1 * cdalp1 - salp1 * sdalp1);
+                                salp1 = s;
+                                calp1 = c;
+                                tripn = v.abs() <= 16.0 * TOL0;
+                                continue;
+                            }
+                        }
+                    }
+                    //Bisection when Newton's method overshoots
+                    let (s, c) = norm((salp1a + salp1b) / 2.0, (calp1a + calp1b) / 2.0);
+                    salp1 = s;
+                    calp1 = c;
+                    tripn = false;
+                    let tolb = TOL0 * TOL0.sqrt();
+                    tripb = (salp1a - salp1).abs() + (calp1a - calp1) < tolb || (salp1 - salp1b).abs() + (calp1 - calp1b) < tolb;
+                }
+                salp2 = state[1];
+                calp2 = state[2];
+                sig12 = state[3];
+                let lengths = self.lengths(state[8], sig12, state[4], state[5], dn1, state[6], state[7], dn2, cbet1, cbet2);
+                s12x = lengths.s12b * self.b;
+                m12x = lengths.m12b * self.b;
+                big_m12 = lengths.big_m12;
+                big_m21 = lengths.big_m21;
+            }
+        }
+        if swapp < 0.0 {
+            ::std::mem::swap(&mut salp1, &mut salp2);
+            ::std::mem::swap(&mut calp1, &mut calp2);
+            ::std::mem::swap(&mut big_m12, &mut big_m21);
+        }
+        salp1 *= swapp * lonsign;
+        calp1 *= swapp * latsign;
+        salp2 *= swapp * lonsign;
+        calp2 *= swapp * latsign;
+        KarneyInverse {
+            distance: s12x,
+            initial_azimuth: wrap_azimuth(salp1.atan2(calp1)),
+            final_azimuth: wrap_azimuth(salp2.atan2(calp2)),
+            arc_length: sig12,
+            reduced_length: m12x,
+            geodesic_scale_12: big_m12,
+            geodesic_scale_21: big_m21
+        }
+    }
+
+    fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> KarneyDirect {
+        let (salp1, calp1) = sin_cos_degrees(ang_round(ang_normalize(azi1)));
+        let (sbet1, cbet1) = sin_cos_degrees(ang_round(lat_fix(lat1)));
+        let (sbet1, cbet1) = norm(self.f1 * sbet1, cbet1);
+        let cbet1 = cbet1.max(TINY);
+        let dn1 = (1.0 + self.ep2 * sbet1.powi(2)).sqrt();
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+        let somg1 = salp0 * sbet1;
+        let comg1 = if sbet1 != 0.0 || calp1 != 0.0 { calp1 * cbet1 } else { 1.0 };
+        let (ssig1, csig1) = norm(sbet1, comg1);
+        let k2 = calp0.powi(2) * self.ep2;
+        let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+        let a1m1 = a1m1f(eps);
+        let c1a = c1f(eps);
+        let b11 = sin_cos_series(ssig1, csig1, &c1a);
+        let (s, c) = b11.sin_cos();
+        let stau1 = ssig1 * c + csig1 * s;
+        let ctau1 = csig1 * c - ssig1 * s;
+        let c1pa = c1pf(eps);
+        let a2m1 = a2m1f(eps);
+        let c2a = c2f(eps);
+        let b21 = sin_cos_series(ssig1, csig1, &c2a);
+        let c3a = self.c3f(eps);
+        let a3c = -self.f * salp0 * self.a3f(eps);
+        let b31 = sin_cos_series(ssig1, csig1, &c3a);
+        //Arc length from the distance by the reverted series
+        let tau12 = s12 / (self.b * (1.0 + a1m1));
+        let (s, c) = tau12.sin_cos();
+        let b12 = -sin_cos_series(stau1 * c + ctau1 * s, ctau1 * c - stau1 * s, &c1pa);
+        let mut sig12 = tau12 - (b12 - b11);
+        let (mut ssig12, mut csig12) = sig12.sin_cos();
+        if self.f.abs() > 0.01 {
+            //One Newton step for strongly flattened ellipsoids, where the series alone is not accurate
+            let ssig2 = ssig1 * csig12 + csig1 * ssig12;
+            let csig2 = csig1 * csig12 - ssig1 * ssig12;
+            let b12 = sin_cos_series(ssig2, csig2, &c1a);
+            let serr = (1.0 + a1m1) * (sig12 + (b12 - b11)) - s12 / self.b;
+            sig12 -= serr / (1.0 + k2 * ssig2.powi(2)).sqrt();
+            let (s, c) = sig12.sin_cos();
+            ssig12 = s;
+            csig12 = c;
+        }
+        let ssig2 = ssig1 * csig12 + csig1 * ssig12;
+        let mut csig2 = csig1 * csig12 - ssig1 * ssig12;
+        let dn2 = (1.0 + k2 * ssig2.powi(2)).sqrt();
+        let b12 = sin_cos_series(ssig2, csig2, &c1a);
+        let ab1 = (1.0 + a1m1) * (b12 - b11);
+        let sbet2 = calp0 * ssig2;
+        let mut cbet2 = salp0.hypot(calp0 * csig2);
+        if cbet2 == 0.0 {
+            cbet2 = TINY;
+            csig2 = TINY;
+        }
+        let salp2 = salp0;
+        let calp2 = calp0 * csig2;
+        let somg2 = salp0 * ssig2;
+        let comg2 = csig2;
+        let omg12 = (somg2 * comg1 - comg2 * somg1).atan2(comg2 * comg1 + somg2 * somg1);
+        let lam12 = omg12 + a3c * (sig12 + (sin_cos_series(ssig2, csig2, &c3a) - b31));
+        let lon2 = ang_normalize(ang_normalize(lon1) + ang_normalize(lam12.to_degrees()));
+        let lat2 = sbet2.atan2(self.f1 * cbet2);
+        let b22 = sin_cos_series(ssig2, csig2, &c2a);
+        let ab2 = (1.0 + a2m1) * (b22 - b21);
+        let j12 = (a1m1 - a2m1) * sig12 + (ab1 - ab2);
+        let t = k2 * (ssig2 - ssig1) * (ssig2 + ssig1) / (dn1 + dn2);
+        KarneyDirect {
+            destination: Vector3::new(lat2, wrap_longitude(lon2.to_radians()), 0.0),
+            final_azimuth: wrap_azimuth(salp2.atan2(calp2)),
+            arc_length: sig12,
+            reduced_length: self.b * ((dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2)) - csig1 * csig2 * j12),
+            geodesic_scale_12: csig12 + (t * ssig2 - csig2 * j12) * ssig1 / dn1,
+            geodesic_scale_21: csig12 - (t * ssig1 - csig1 * j12) * ssig2 / dn2
+        }
+    }
+}
+
+/// Horner evaluation of the polynomial of degree n with coefficients p[0..=n], highest first
+fn polyval(n: usize, p: &[f64], x: f64) -> f64 {
+    p[1..=n].iter().fold(p[0], |y, coeff| y * x + coeff)
+}
+
+/// A1 - 1 of the distance integral
+fn a1m1f(eps: f64) -> f64 {
+    let eps2 = eps.powi(2);
+    let t = eps2 * (eps2 * (eps2 + 4.0) + 64.0) / 256.0;
+    (t + eps) / (1.0 - eps)
+}
+
+/// A2 - 1 of the reduced length integral
+fn a2m1f(eps: f64) -> f64 {
+    let eps2 = eps.powi(2);
+    let t = eps2 * (eps2 * (-11.0 * eps2 - 28.0) - 192.0) / 256.0;
+    (t - eps) / (1.0 + eps)
+}
+
+/// Fourier coefficients of a series in eps with the given coefficient table
+fn series_coefficients(eps: f64, coeff: &[f64]) -> [f64; ORDER + 1] {
+    let eps2 = eps.powi(2);
+    let mut c = [0.0; ORDER + 1];
+    let (mut d, mut o) = (eps, 0);
+    for (l, c_l) in c.iter_mut().enumerate().skip(1) {
+        let m = (ORDER - l) / 2;
+        *c_l = d * polyval(m, &coeff[o..], eps2) / coeff[o + m + 1];
+        o += m + 2;
+        d *= eps;
+    }
+    c
+}
+
+/// C1 coefficients of the distance integral
+fn c1f(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [f64; 18] = [-1.0, 6.0, -16.0, 32.0, -9.0, 64.0, -128.0, 2048.0, 9.0, -16.0, 768.0,
+                              3.0, -5.0, 512.0, -7.0, 1280.0, -7.0, 2048.0];
+    series_coefficients(eps, &COEFF)
+}
+
+/// C1' coefficients of the reverted distance integral
+fn c1pf(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [f64; 18] = [205.0, -432.0, 768.0, 1536.0, 4005.0, -4736.0, 3840.0, 12288.0, -225.0, 116.0, 384.0,
+                              -7173.0, 2695.0, 7680.0, 3467.0, 7680.0, 38081.0, 61440.0];
+    series_coefficients(eps, &COEFF)
+}
+
+/// C2 coefficients of the reduced length integral
+fn c2f(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [f64; 18] = [1.0, 2.0, 16.0, 32.0, 35.0, 64.0, 384.0, 2048.0, 15.0, 80.0, 768.0,
+                              7.0, 35.0, 512.0, 63.0, 1280.0, 77.0, 2048.0];
+    series_coefficients(eps, &COEFF)
+}
+
+/// Sum of c[l] * sin(2 * l * x) for l >= 1 by Clenshaw summation
+fn sin_cos_series(sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    let mut k = c.len();
+    let mut n = k - 1;
+    let ar = 2.0 * (cosx - sinx) * (cosx + sinx);
+    let mut y1 = 0.0;
+    let mut y0 = if n & 1 == 1 {
+        k -= 1;
+        c[k]
+    } else {
+        0.0
+    };
+    n /= 2;
+    while n > 0 {
+        n -= 1;
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+    2.0 * sinx * cosx * y0
+}
+
+/// Root k of the astroid equation for the nearly antipodal starting guess
+fn astroid(x: f64, y: f64) -> f64 {
+    let p = x.powi(2);
+    let q = y.powi(2);
+    let r = (p + q - 1.0) / 6.0;
+    if q == 0.0 && r <= 0.0 {
+        return 0.0;
+    }
+    let s = p * q / 4.0;
+    let r2 = r.powi(2);
+    let r3 = r * r2;
+    let disc = s * (s + 2.0 * r3);
+    let mut u = r;
+    if disc >= 0.0 {
+        let mut t3 = s + r3;
+        t3 += if t3 < 0.0 { -disc.sqrt() } else { disc.sqrt() };
+        let t = t3.cbrt();
+        u += t + if t != 0.0 { r2 / t } else { 0.0 };
+    } else {
+        let ang = (-disc).sqrt().atan2(-(s + r3));
+        u += 2.0 * r * (ang / 3.0).cos();
+    }
+    let v = (u.powi(2) + q).sqrt();
+    let uv = if u < 0.0 { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2.0 * v);
+    uv / ((uv + w.powi(2)).sqrt() + w)
+}
+
+/// max(x, 0) with a positive zero, so that atan2 of a signed zero cannot flip to -pi
+fn positive_part(x: f64) -> f64 {
+    if x > 0.0 { x } else { 0.0 }
+}
+
+fn norm(sinx: f64, cosx: f64) -> (f64, f64) {
+    let r = sinx.hypot(cosx);
+    (sinx / r, cosx / r)
+}
+
+/// Sine and cosine of an angle in degrees, exact at multiples of 90 degrees
+fn sin_cos_degrees(x: f64) -> (f64, f64) {
+    let r = x % 360.0;
+    let q = (r / 90.0).round();
+    let (s, c) = (r - 90.0 * q).to_radians().sin_cos();
+    match (q as i64).rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s)
+    }
+}
+
+/// Rounds tiny angles (degrees) so that they are exact multiples of 2^-53 / 16
+fn ang_round(x: f64) -> f64 {
+    let z = 1.0 / 16.0;
+    let y = x.abs();
+    let y = if y < z { z - (z - y) } else { y };
+    y.copysign(x)
+}
+
+/// Reduces an angle in degrees to [-180, 180)
+fn ang_normalize(x: f64) -> f64 {
+    let y = x % 360.0;
+    if y >= 180.0 {
+        y - 360.0
+    } else if y < -180.0 {
+        y + 360.0
+    } else {
+        y
+    }
+}
+
+/// Difference lon2 - lon1 in degrees reduced to [-180, 180]
+fn ang_diff(x: f64, y: f64) -> f64 {
+    let d = ang_normalize(y - x);
+    if d == -180.0 && y - x > 0.0 { 180.0 } else { d }
+}
+
+/// Latitude in degrees, NaN outside [-90, 90]
+fn lat_fix(x: f64) -> f64 {
+    if x.abs() > 90.0 { f64::NAN } else { x }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo;
+
+    fn wgs84() -> geo_ellipsoid::GeoEllipsoid {
+        geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS, geo_ellipsoid::WGS84_FLATTENING)
+    }
+
+    fn lla_degrees(lat: f64, lon: f64) -> Vector3<f64> {
+        Vector3::new(lat.to_radians(), lon.to_radians(), 0.0)
+    }
+
+    #[test]
+    fn test_wgs84_variants() {
+        let (jfk, lhr) = (lla_degrees(40.6, -73.8), lla_degrees(51.6, -0.5));
+        assert_eq!(geodesic_inverse_wgs84(&jfk, &lhr), geodesic_inverse(&jfk, &lhr, &wgs84()));
+        assert_eq!(geodesic_direct_wgs84(&jfk, 0.9, 5.0e6), geodesic_direct(&jfk, 0.9, 5.0e6, &wgs84()));
+    }
+
+    #[test]
+    fn test_geodesic_inverse() {
+        let ellipsoid = wgs84();
+        //GeographicLib, Wellington to Salamanca and JFK to LHR
+        let geodesic = geodesic_inverse(&lla_degrees(-41.32, 174.81), &lla_degrees(40.96, -5.50), &ellipsoid);
+        assert_approx_eq!(geodesic.distance, 19959679.267353814, 1e-7);
+        assert_approx_eq!(geodesic.initial_azimuth.to_degrees(), 161.06766998615882, 1e-11);
+        assert_approx_eq!(geodesic.final_azimuth.to_degrees(), 18.825195123248392, 1e-11);
+        let geodesic = geodesic_inverse(&lla_degrees(40.6, -73.8), &lla_degrees(51.6, -0.5), &ellipsoid);
+        assert_approx_eq!(geodesic.distance, 5551759.400319, 1e-6);
+        assert_approx_eq!(geodesic.initial_azimuth.to_degrees(), 51.198882845579824, 1e-11);
+        assert_approx_eq!(geodesic.final_azimuth.to_degrees(), 107.82177673551425, 1e-11);
+        //Nearly antipodal example of Karney (2013), where Vincenty's method does not converge
+        let start = lla_degrees(-30.0, 0.0);
+        let end = lla_degrees(29.9, 179.8);
+        assert!(geo::vincenty_inverse(&start, &end, &ellipsoid).is_err());
+        let geodesic = geodesic_inverse(&start, &end, &ellipsoid);
+        assert_approx_eq!(geodesic.distance, 19989832.827610, 1e-6);
+        assert_approx_eq!(geodesic.initial_azimuth.to_degrees(), 161.890524736, 1e-9);
+        assert_approx_eq!(geodesic.final_azimuth.to_degrees(), 18.090737246, 1e-9);
+        //GeographicLib example of a failure of Vincenty's method
+        let geodesic = geodesic_inverse(&lla_degrees(0.0, 0.0), &lla_degrees(0.5, 179.5), &ellipsoid);
+        assert_approx_eq!(geodesic.distance, 19936288.579, 1e-3);
+        //Exact antipodes on the equator are joined by the meridians, half of the meridian ellipse
+        let half_meridian = 2.0 * geo::meridian_arc_length(f64::consts::FRAC_PI_2, &ellipsoid);
+        let geodesic = geodesic_inverse(&lla_degrees(0.0, 0.0), &lla_degrees(0.0, 180.0), &ellipsoid);
+        assert_approx_eq!(geodesic.distance, half_meridian, 1e-6);
+        assert_approx_eq!(geodesic.distance, 20003931.4586254, 1e-6);
+        let geodesic = geodesic_inverse(&lla_degrees(90.0, 0.0), &lla_degrees(-90.0, 0.0), &ellipsoid);
+        assert_approx_eq!(geodesic.distance, half_meridian, 1e-6);
+        //Short equatorial lines follow the equator
+        let geodesic = geodesic_inverse(&lla_degrees(0.0, 0.0), &lla_degrees(0.0, 90.0), &ellipsoid);
+        assert_approx_eq!(geodesic.distance, ellipsoid.get_semi_major_axis() * f64::consts::FRAC_PI_2, 1e-6);
+        assert_approx_eq!(geodesic.initial_azimuth, f64::consts::FRAC_PI_2, 1e-15);
+        //Coincident points
+        let geodesic = geodesic_inverse(&start, &start, &ellipsoid);
+        assert_eq!(geodesic.distance, 0.0);
+        assert_eq!(geodesic.reduced_length, 0.0);
+        assert_approx_eq!(geodesic.geodesic_scale_12, 1.0, 1e-15);
+        //On a sphere the geodesic is the great circle
+        let sphere = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::MEAN_EARTH_RADIUS_METERS, f64::INFINITY);
+        let start = lla_degrees(48.856614, 2.352222);
+        let end = lla_degrees(40.712776, -74.005974);
+        let geodesic = geodesic_inverse(&start, &end, &sphere);
+        let haversine = geo::haversine_distance(&start, &end, geo_ellipsoid::MEAN_EARTH_RADIUS_METERS);
+        assert_approx_eq!(geodesic.distance, haversine, 1e-6);
+        let sigma = haversine / geo_ellipsoid::MEAN_EARTH_RADIUS_METERS;
+        assert_approx_eq!(geodesic.reduced_length, geo_ellipsoid::MEAN_EARTH_RADIUS_METERS * sigma.sin(), 1e-6);
+        assert_approx_eq!(geodesic.geodesic_scale_12, sigma.cos(), 1e-12);
+    }
+
+    #[test]
+    fn test_geodesic_direct() {
+        let ellipsoid = wgs84();
+        //Worked example of Karney (2013)
+        let start = lla_degrees(40.0, 0.0);
+        let direct = geodesic_direct(&start, 30f64.to_radians(), 10.0e6, &ellipsoid);
+        assert_approx_eq!(direct.destination.x.to_degrees(), 41.79331020506, 1e-11);
+        assert_approx_eq!(direct.destination.y.to_degrees(), 137.84490004377, 1e-11);
+        assert_approx_eq!(direct.final_azimuth.to_degrees(), 149.09016931807, 1e-11);
+        //The reduced length is the sensitivity of the destination to the initial azimuth
+        let h = 1e-7;
+        let left = geodesic_direct(&start, 30f64.to_radians() - h, 10.0e6, &ellipsoid);
+        let right = geodesic_direct(&start, 30f64.to_radians() + h, 10.0e6, &ellipsoid);
+        let separation = geodesic_inverse(&left.destination, &right.destination, &ellipsoid).distance;
+        assert_approx_eq!(separation / (2.0 * h), direct.reduced_length, 1e-1);
+        //And M21 its derivative along the geodesic
+        let ds = 1.0;
+        let behind = geodesic_direct(&start, 30f64.to_radians(), 10.0e6 - ds, &ellipsoid);
+        let ahead = geodesic_direct(&start, 30f64.to_radians(), 10.0e6 + ds, &ellipsoid);
+        assert_approx_eq!((ahead.reduced_length - behind.reduced_length) / (2.0 * ds), direct.geodesic_scale_21, 1e-9);
+        //Inverse of the destination gives back the same geodesic
+        let inverse = geodesic_inverse(&start, &direct.destination, &ellipsoid);
+        assert_approx_eq!(inverse.distance, 10.0e6, 1e-8);
+        assert_approx_eq!(inverse.initial_azimuth, 30f64.to_radians(), 1e-14);
+        assert_approx_eq!(inverse.reduced_length, direct.reduced_length, 1e-7);
+        assert_approx_eq!(inverse.geodesic_scale_12, direct.geodesic_scale_12, 1e-12);
+        assert_approx_eq!(inverse.geodesic_scale_21, direct.geodesic_scale_21, 1e-12);
+        //The altitude of the start is kept
+        let direct = geodesic_direct(&Vector3::new(0.1, 0.2, 350.0), 1.0, 1000.0, &ellipsoid);
+        assert_eq!(direct.destination.z, 350.0);
+        //Along the equator to the antimeridian the longitude stays in (-pi, pi]
+        let quarter = ellipsoid.get_semi_major_axis() * f64::consts::FRAC_PI_2;
+        for &(lon, azimuth) in [(-f64::consts::FRAC_PI_2, 1.5 * f64::consts::PI), (f64::consts::FRAC_PI_2, f64::consts::FRAC_PI_2)].iter() {
+            let lon2 = geodesic_direct(&Vector3::new(0.0, lon, 0.0), azimuth, quarter, &ellipsoid).destination.y;
+            assert!(lon2 > -f64::consts::PI && lon2 <= f64::consts::PI);
+            assert_approx_eq!(lon2.abs(), f64::consts::PI, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_geodesic_round_trip() {
+        //Random pairs, half of them nearly antipodal, must all converge and round trip
+        let ellipsoid = wgs84();
+        let mut seed = 7u64;
+        let mut next = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        for i in 0..1000 {
+            let lat1 = (next() * 2.0 - 1.0).asin().to_degrees();
+            let lon1 = next() * 360.0 - 180.0;
+            let (lat2, lon2) = if i % 2 == 0 {
+                ((next() * 2.0 - 1.0).asin().to_degrees(), next() * 360.0 - 180.0)
+            } else {
+                (-lat1 + (next() - 0.5), lon1 + 180.0 + (next() - 0.5))
+            };
+            let start = lla_degrees(lat1, lon1);
+            let end = lla_degrees(lat2, lon2);
+            let inverse = geodesic_inverse(&start, &end, &ellipsoid);
+            assert!(inverse.distance.is_finite());
+            let direct = geodesic_direct(&start, inverse.initial_azimuth, inverse.distance, &ellipsoid);
+            let miss = geodesic_inverse(&direct.destination, &end, &ellipsoid).distance;
+            assert!(miss < 1.5e-8, "miss {} for {} {} {} {}", miss, lat1, lon1, lat2, lon2);
+            assert_approx_eq!(direct.reduced_length, inverse.reduced_length, 1e-6);
+        }
+    }
+}
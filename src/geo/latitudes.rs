@@ -0,0 +1,278 @@
+use std::f64;
+use geo::{meridian_arc_length, latitude_from_meridian_arc, rectifying_radius};
+use structs::geo_ellipsoid;
+use projections::{authalic_q, isometric_latitude, isometric_latitude_inverse};
+
+/// Converts a geodetic latitude to the isometric latitude
+/// 
+/// # Arguments
+/// 
+/// * `lat` - f64 geodetic latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - isometric latitude, infinite at the poles (radians)
+/// 
+/// # Formula
+/// 
+/// * psi = asinh(tan(lat)) - e * atanh(e * sin(lat)), closed form
+pub fn geodetic2isometric(lat: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    isometric_latitude(lat, ellipsoid.get_first_ecc())
+}
+
+/// Converts an isometric latitude to the geodetic latitude
+/// 
+/// # Arguments
+/// 
+/// * `psi` - f64 isometric latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - geodetic latitude (radians)
+/// 
+/// # Formula
+/// 
+/// * lat by Newton's method on psi(lat), starting from atan(sinh(psi)), dpsi / dlat = (1 - e^2) / ((1 - e^2 * sin(lat)^2) * cos(lat))
+pub fn isometric2geodetic(psi: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    isometric_latitude_inverse(psi, ellipsoid.get_first_ecc())
+}
+
+/// Converts a geodetic latitude to the conformal latitude
+/// 
+/// # Arguments
+/// 
+/// * `lat` - f64 geodetic latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - conformal latitude (radians)
+/// 
+/// # Formula
+/// 
+/// * chi = atan(sinh(geodetic2isometric(lat))), closed form
+pub fn geodetic2conformal(lat: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    geodetic2isometric(lat, ellipsoid).sinh().atan()
+}
+
+/// Converts a conformal latitude to the geodetic latitude
+/// 
+/// # Arguments
+/// 
+/// * `chi` - f64 conformal latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - geodetic latitude (radians)
+/// 
+/// # Formula
+/// 
+/// * lat = chi + (e^2/2 + 5e^4/24 + e^6/12 + 13e^8/360) * sin(2 chi)
+///   + (7e^4/48 + 29e^6/240 + 811e^8/11520) * sin(4 chi)
+///   + (7e^6/120 + 81e^8/1120) * sin(6 chi) + 4279e^8/161280 * sin(8 chi)
+/// 
+/// The series (Snyder 3-5) is carried to order e^8. The truncation error is below 1e-10 radians
+/// for flattenings up to 1/150.
+pub fn conformal2geodetic(chi: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    let e2 = ellipsoid.get_first_ecc_squared();
+    let (e4, e6, e8) = (e2.powi(2), e2.powi(3), e2.powi(4));
+    chi + (e2 / 2.0 + 5.0 * e4 / 24.0 + e6 / 12.0 + 13.0 * e8 / 360.0) * (2.0 * chi).sin()
+        + (7.0 * e4 / 48.0 + 29.0 * e6 / 240.0 + 811.0 * e8 / 11520.0) * (4.0 * chi).sin()
+        + (7.0 * e6 / 120.0 + 81.0 * e8 / 1120.0) * (6.0 * chi).sin()
+        + 4279.0 * e8 / 161280.0 * (8.0 * chi).sin()
+}
+
+/// Converts a geodetic latitude to the authalic latitude
+/// 
+/// # Arguments
+/// 
+/// * `lat` - f64 geodetic latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - authalic latitude (radians)
+/// 
+/// # Formula
+/// 
+/// * q = (1 - e^2) * (sin(lat) / (1 - e^2 * sin(lat)^2) - ln((1 - e * sin(lat)) / (1 + e * sin(lat))) / (2e))
+/// * beta = asin(q / q(pi / 2)), closed form
+/// 
+/// On a sphere the latitude is returned unchanged, avoiding the ill-conditioned asin near the poles.
+pub fn geodetic2authalic(lat: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    let e = ellipsoid.get_first_ecc();
+    if e == 0.0 {
+        return lat;
+    }
+    (authalic_q(lat, e) / authalic_q(f64::consts::FRAC_PI_2, e)).clamp(-1.0, 1.0).asin()
+}
+
+/// Converts an authalic latitude to the geodetic latitude
+/// 
+/// # Arguments
+/// 
+/// * `beta` - f64 authalic latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - geodetic latitude (radians)
+/// 
+/// # Formula
+/// 
+/// * lat = beta + (e^2/3 + 31e^4/180 + 517e^6/5040) * sin(2 beta)
+///   + (23e^4/360 + 251e^6/3780) * sin(4 beta) + 761e^6/45360 * sin(6 beta)
+/// * refined by one Newton step, lat += (q(beta) - q(lat)) * (1 - e^2 * sin(lat)^2)^2 / (2 * (1 - e^2) * cos(lat))
+/// 
+/// The series (Snyder 3-18) is carried to order e^6, which alone leaves errors of about 4e-9
+/// radians at a flattening of 1/150. The Newton step brings this below 1e-12 radians.
+pub fn authalic2geodetic(beta: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    let e = ellipsoid.get_first_ecc();
+    let e2 = e.powi(2);
+    let (e4, e6) = (e2.powi(2), e2.powi(3));
+    let lat = beta + (e2 / 3.0 + 31.0 * e4 / 180.0 + 517.0 * e6 / 5040.0) * (2.0 * beta).sin()
+        + (23.0 * e4 / 360.0 + 251.0 * e6 / 3780.0) * (4.0 * beta).sin()
+        + 761.0 * e6 / 45360.0 * (6.0 * beta).sin();
+    //The series is exact at the poles, where the Newton step would divide by cos(lat) = 0
+    if e == 0.0 || beta.abs() > f64::consts::FRAC_PI_2 - 1e-6 {
+        return lat;
+    }
+    let q = beta.sin() * authalic_q(f64::consts::FRAC_PI_2, e);
+    lat + (q - authalic_q(lat, e)) * (1.0 - e2 * lat.sin().powi(2)).powi(2) / (2.0 * (1.0 - e2) * lat.cos())
+}
+
+/// Converts a geodetic latitude to the rectifying latitude
+/// 
+/// # Arguments
+/// 
+/// * `lat` - f64 geodetic latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - rectifying latitude (radians)
+/// 
+/// # Formula
+/// 
+/// * mu = meridian_arc_length(lat) / A, with A the rectifying radius
+/// 
+/// The meridian arc series is carried to order n^4 in the third flattening, with truncation
+/// errors below 1e-12 radians for flattenings up to 1/150.
+pub fn geodetic2rectifying(lat: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    if ellipsoid.get_flattening() == 0.0 {
+        return lat;
+    }
+    meridian_arc_length(lat, ellipsoid) / rectifying_radius(ellipsoid)
+}
+
+/// Converts a rectifying latitude to the geodetic latitude
+/// 
+/// # Arguments
+/// 
+/// * `mu` - f64 rectifying latitude (radians)
+/// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+/// 
+/// # Return Value
+/// 
+/// * f64 - geodetic latitude (radians)
+/// 
+/// # Formula
+/// 
+/// * lat = latitude_from_meridian_arc(mu * A), the order n^4 inverse series refined by Newton's method
+pub fn rectifying2geodetic(mu: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> f64 {
+    if ellipsoid.get_flattening() == 0.0 {
+        return mu;
+    }
+    latitude_from_meridian_arc(mu * rectifying_radius(ellipsoid), ellipsoid)
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clarke_1866() -> geo_ellipsoid::GeoEllipsoid {
+        geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::CLARKE_1866_SEMI_MAJOR_AXIS_METERS,
+                                         geo_ellipsoid::CLARKE_1866_FLATTENING)
+    }
+
+    #[test]
+    fn test_isometric() {
+        let ellipsoid = clarke_1866();
+        //Snyder's ellipsoidal Mercator example at 35 N, y = a * psi = 4139145.6 m
+        let psi = geodetic2isometric(35.0f64.to_radians(), &ellipsoid);
+        assert_approx_eq!(psi * ellipsoid.get_semi_major_axis(), 4139145.6, 0.1);
+        assert_approx_eq!(isometric2geodetic(psi, &ellipsoid), 35.0f64.to_radians(), 1e-12);
+        assert_eq!(geodetic2isometric(0.0, &ellipsoid), 0.0);
+    }
+
+    #[test]
+    fn test_conformal() {
+        let ellipsoid = clarke_1866();
+        //The conformal latitude is the Gudermannian of the isometric latitude
+        let chi = geodetic2conformal(35.0f64.to_radians(), &ellipsoid);
+        assert_approx_eq!(chi, (4139145.6 / ellipsoid.get_semi_major_axis()).sinh().atan(), 1e-7);
+        assert!(chi < 35.0f64.to_radians());
+        assert_approx_eq!(geodetic2conformal(f64::consts::FRAC_PI_2, &ellipsoid), f64::consts::FRAC_PI_2, 1e-15);
+    }
+
+    #[test]
+    fn test_authalic() {
+        let ellipsoid = clarke_1866();
+        //Snyder's ellipsoidal Albers example, q at 23, 29.5, 35 and 45.5 N and the pole
+        let q_pole: f64 = 1.9954814;
+        for &(lat_deg, q) in [(23.0f64, 0.7767080f64), (29.5, 0.9792529), (35.0, 1.1410831), (45.5, 1.4201080)].iter() {
+            assert_approx_eq!(geodetic2authalic(lat_deg.to_radians(), &ellipsoid), (q / q_pole).asin(), 1e-7);
+        }
+        assert_approx_eq!(geodetic2authalic(f64::consts::FRAC_PI_2, &ellipsoid), f64::consts::FRAC_PI_2, 1e-15);
+    }
+
+    #[test]
+    fn test_rectifying() {
+        let ellipsoid = clarke_1866();
+        //Quarter meridian of Clarke 1866, 10001888.04 m
+        assert_approx_eq!(rectifying_radius(&ellipsoid) * f64::consts::FRAC_PI_2, 10001888.04, 0.01);
+        assert_approx_eq!(geodetic2rectifying(f64::consts::FRAC_PI_2, &ellipsoid), f64::consts::FRAC_PI_2, 1e-15);
+        assert!(geodetic2rectifying(45.0f64.to_radians(), &ellipsoid) < 45.0f64.to_radians());
+    }
+
+    #[test]
+    fn test_inverses() {
+        let flattenings = [geo_ellipsoid::CLARKE_1866_FLATTENING, geo_ellipsoid::WGS84_FLATTENING, 150.0];
+        for inv_f in flattenings.iter() {
+            let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(6378137.0, *inv_f);
+            let mut lat_deg: f64 = -89.5;
+            while lat_deg < 90.0 {
+                let lat = lat_deg.to_radians();
+                assert_approx_eq!(isometric2geodetic(geodetic2isometric(lat, &ellipsoid), &ellipsoid), lat, 1e-9);
+                assert_approx_eq!(conformal2geodetic(geodetic2conformal(lat, &ellipsoid), &ellipsoid), lat, 1e-9);
+                assert_approx_eq!(authalic2geodetic(geodetic2authalic(lat, &ellipsoid), &ellipsoid), lat, 1e-12);
+                assert_approx_eq!(rectifying2geodetic(geodetic2rectifying(lat, &ellipsoid), &ellipsoid), lat, 1e-12);
+                lat_deg += 0.5;
+            }
+        }
+        //On a sphere every auxiliary latitude is the geodetic latitude
+        let sphere = geo_ellipsoid::GeoEllipsoid::new(6371000.0, f64::INFINITY);
+        let lat = 0.7;
+        assert_approx_eq!(geodetic2conformal(lat, &sphere), lat, 1e-15);
+        assert_approx_eq!(geodetic2authalic(lat, &sphere), lat, 1e-15);
+        assert_approx_eq!(geodetic2rectifying(lat, &sphere), lat, 1e-15);
+        assert_approx_eq!(authalic2geodetic(lat, &sphere), lat, 1e-15);
+    }
+
+    #[test]
+    fn test_sphere() {
+        let sphere = geo_ellipsoid::GeoEllipsoid::authalic_sphere();
+        for &lat in [0.0, 0.3, -1.2, f64::consts::FRAC_PI_2 - 1e-7, 1e-8 - f64::consts::FRAC_PI_2, f64::consts::FRAC_PI_2].iter() {
+            assert_eq!(geodetic2authalic(lat, &sphere), lat);
+            assert_eq!(authalic2geodetic(lat, &sphere), lat);
+            assert_eq!(geodetic2rectifying(lat, &sphere), lat);
+            assert_eq!(rectifying2geodetic(lat, &sphere), lat);
+            assert_eq!(conformal2geodetic(lat, &sphere), lat);
+            assert_approx_eq!(geodetic2conformal(lat, &sphere), lat, 1e-15);
+        }
+    }
+}
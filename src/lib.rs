@@ -2,11 +2,19 @@
 #[macro_use] 
 extern crate assert_approx_eq;
 extern crate nalgebra as na;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 /// Module for 3-dimensional coordinate transformations
 pub mod d3;
 /// Module for 2-dimensional coordinate transformations
 pub mod d2;
 ///Module for geographical coordinate transformations
 pub mod geo;
+/// Module for map projections
+pub mod projections;
+/// Module for converting between coordinate reference systems by EPSG code
+pub mod crs;
 /// Module for constants used in the coordinate transformations
 pub mod structs;
\ No newline at end of file
@@ -0,0 +1,259 @@
+use na::Vector3;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::{Projection, authalic_q, authalic_q_inverse};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Constructor arguments of an Albers equal-area projection, its serialized form
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Definition {
+    lat0: f64,
+    lon0: f64,
+    sp1: f64,
+    sp2: f64,
+    false_easting: f64,
+    false_northing: f64,
+    ellipsoid: geo_ellipsoid::GeoEllipsoid
+}
+
+/// Ellipsoidal Albers equal-area conic projection with two standard parallels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlbersEqualArea {
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64,
+    semi_major_axis: f64,
+    ecc: f64,
+    cone_constant: f64,
+    c: f64,
+    origin_radius: f64,
+    definition: Definition
+}
+
+impl AlbersEqualArea {
+    /// Creates an Albers equal-area projection
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat0` - latitude of false origin (radians)
+    /// * `lon0` - longitude of false origin (radians)
+    /// * `sp1` - first standard parallel (radians)
+    /// * `sp2` - second standard parallel (radians)
+    /// * `false_easting` - easting of the false origin (meters)
+    /// * `false_northing` - northing of the false origin (meters)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// 
+    /// # Formula
+    /// 
+    /// * m = cos(lat) / sqrt(1 - e^2 * sin(lat)^2), q = authalic q of lat
+    /// * n = (m1^2 - m2^2) / (q2 - q1), C = m1^2 + n * q1, r0 = a * sqrt(C - n * q0) / n
+    /// 
+    /// Equal standard parallels reduce to the tangent cone, n = sin(sp1).
+    pub fn new(lat0: f64, lon0: f64, sp1: f64, sp2: f64, false_easting: f64, false_northing: f64,
+               ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> AlbersEqualArea {
+        let ecc = ellipsoid.get_first_ecc();
+        let a = ellipsoid.get_semi_major_axis();
+        let m = |lat: f64| lat.cos() / (1.0 - (ecc * lat.sin()).powi(2)).sqrt();
+        let (m1, q1) = (m(sp1), authalic_q(sp1, ecc));
+        let cone_constant = if (sp1 - sp2).abs() < 1e-12 {
+            sp1.sin()
+        } else {
+            (m1.powi(2) - m(sp2).powi(2)) / (authalic_q(sp2, ecc) - q1)
+        };
+        let c = m1.powi(2) + cone_constant * q1;
+        AlbersEqualArea {
+            lon0,
+            false_easting,
+            false_northing,
+            semi_major_axis: a,
+            ecc,
+            cone_constant,
+            c,
+            origin_radius: a * (c - cone_constant * authalic_q(lat0, ecc)).sqrt() / cone_constant,
+            definition: Definition { lat0, lon0, sp1, sp2, false_easting, false_northing, ellipsoid: *ellipsoid }
+        }
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_false_easting(&self) -> f64 {
+        self.false_easting
+    }
+
+    pub fn get_false_northing(&self) -> f64 {
+        self.false_northing
+    }
+
+    /// Cone constant n
+    pub fn get_cone_constant(&self) -> f64 {
+        self.cone_constant
+    }
+
+    /// Scale factor along the parallel of a latitude in radians, 1 on the standard parallels
+    /// 
+    /// # Formula
+    /// 
+    /// * k = r * n / (a * m), the meridian scale is 1 / k so the areal scale is 1 everywhere
+    pub fn parallel_scale(&self, lat: f64) -> f64 {
+        let m = lat.cos() / (1.0 - (self.ecc * lat.sin()).powi(2)).sqrt();
+        self.radius(lat) * self.cone_constant / (self.semi_major_axis * m)
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - easting, northing, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * r = a * sqrt(C - n * q) / n, theta = n * (lon - lon0)
+    /// * easting = FE + r * sin(theta), northing = FN + r0 - r * cos(theta)
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let r = self.radius(lla_vec.x);
+        let theta = self.cone_constant * geo::wrap_longitude(lla_vec.y - self.lon0);
+        Vector3::new(self.false_easting + r * theta.sin(),
+                     self.false_northing + self.origin_radius - r * theta.cos(),
+                     lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (easting, northing, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * r = sqrt(dE^2 + (r0 - dN)^2), q = (C - r^2 * n^2 / a^2) / n
+    /// * theta = atan2(sign(n) * dE, sign(n) * (r0 - dN)), lon = theta / n + lon0
+    /// * lat from q by Newton's method on the authalic latitude
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let sign = self.cone_constant.signum();
+        let d_east = proj_vec.x - self.false_easting;
+        let d_north = self.origin_radius - (proj_vec.y - self.false_northing);
+        let r = d_east.hypot(d_north);
+        let q = (self.c - (r * self.cone_constant / self.semi_major_axis).powi(2)) / self.cone_constant;
+        let theta = (sign * d_east).atan2(sign * d_north);
+        Vector3::new(authalic_q_inverse(q, self.ecc),
+                     geo::wrap_longitude(theta / self.cone_constant + self.lon0),
+                     proj_vec.z)
+    }
+
+    /// Distance from the apex of the cone for a latitude in radians (meters)
+    fn radius(&self, lat: f64) -> f64 {
+        self.semi_major_axis * (self.c - self.cone_constant * authalic_q(lat, self.ecc)).max(0.0).sqrt() / self.cone_constant
+    }
+}
+
+impl Projection for AlbersEqualArea {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.forward(lla_vec))
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+/// Serializes the constructor arguments only
+#[cfg(feature = "serde")]
+impl Serialize for AlbersEqualArea {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.definition.serialize(serializer)
+    }
+}
+
+/// Rebuilds the derived values through the constructor, the ellipsoid is validated by its own deserializer
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for AlbersEqualArea {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<AlbersEqualArea, D::Error> {
+        let definition = Definition::deserialize(deserializer)?;
+        Ok(AlbersEqualArea::new(definition.lat0, definition.lon0, definition.sp1, definition.sp2,
+                                definition.false_easting, definition.false_northing, &definition.ellipsoid))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        //Snyder's worked example on the Clarke 1866 ellipsoid
+        let clarke = geo_ellipsoid::GeoEllipsoid::new(6378206.4, 294.978610787262);
+        let albers = AlbersEqualArea::new(23.0f64.to_radians(), -96.0f64.to_radians(), 29.5f64.to_radians(),
+                                          45.5f64.to_radians(), 0.0, 0.0, &clarke);
+        let proj_vec = albers.forward(&Vector3::new(35.0f64.to_radians(), -75.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 1885472.7, 0.05);
+        assert_approx_eq!(proj_vec.y, 1535925.0, 0.05);
+        //NAD83 / Conus Albers (EPSG:5070) on GRS80, the false origin maps to (0, 0)
+        let grs80 = geo_ellipsoid::GeoEllipsoid::new(6378137.0, 298.257222101);
+        let conus = AlbersEqualArea::new(23.0f64.to_radians(), -96.0f64.to_radians(), 29.5f64.to_radians(),
+                                         45.5f64.to_radians(), 0.0, 0.0, &grs80);
+        let origin = conus.forward(&Vector3::new(23.0f64.to_radians(), -96.0f64.to_radians(), 0.0));
+        assert_approx_eq!(origin.x, 0.0, 1e-6);
+        assert_approx_eq!(origin.y, 0.0, 1e-6);
+        let proj_vec = conus.forward(&Vector3::new(40.0f64.to_radians(), -100.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, -338390.588, 1e-3);
+        assert_approx_eq!(proj_vec.y, 1894100.140, 1e-3);
+    }
+    #[test]
+    fn test_inverse() {
+        let grs80 = geo_ellipsoid::GeoEllipsoid::new(6378137.0, 298.257222101);
+        let albers = AlbersEqualArea::new(-32.0f64.to_radians(), 132.0f64.to_radians(), -18.0f64.to_radians(),
+                                          -36.0f64.to_radians(), 0.0, 0.0, &grs80);
+        for lat_deg in [-89.0f64, -60.0, -32.0, -10.0, 0.0, 30.0, 75.0].iter() {
+            for dlon_deg in [-50.0f64, -7.5, 0.0, 20.0].iter() {
+                let lla_vec = Vector3::new(lat_deg.to_radians(), (132.0 + dlon_deg).to_radians(), 3.0);
+                let back = albers.inverse(&albers.forward(&lla_vec));
+                assert_approx_eq!(back.x, lla_vec.x, 1e-12);
+                assert_approx_eq!(back.y, lla_vec.y, 1e-12);
+                assert_eq!(back.z, 3.0);
+            }
+        }
+    }
+    #[test]
+    fn test_areal_scale() {
+        let grs80 = geo_ellipsoid::GeoEllipsoid::new(6378137.0, 298.257222101);
+        let albers = AlbersEqualArea::new(23.0f64.to_radians(), -96.0f64.to_radians(), 29.5f64.to_radians(),
+                                          45.5f64.to_radians(), 0.0, 0.0, &grs80);
+        let e2 = grs80.get_first_ecc_squared();
+        let a = grs80.get_semi_major_axis();
+        for lat_deg in [29.5f64, 45.5].iter() {
+            let lat = lat_deg.to_radians();
+            assert_approx_eq!(albers.parallel_scale(lat), 1.0, 1e-12);
+            //Projected area of a small cell over its area on the ellipsoid, from central differences
+            let step = 1e-5;
+            let lon = -90.0f64.to_radians();
+            let d_lat = (albers.forward(&Vector3::new(lat + step, lon, 0.0)) - albers.forward(&Vector3::new(lat - step, lon, 0.0))) / (2.0 * step);
+            let d_lon = (albers.forward(&Vector3::new(lat, lon + step, 0.0)) - albers.forward(&Vector3::new(lat, lon - step, 0.0))) / (2.0 * step);
+            let w = 1.0 - e2 * lat.sin().powi(2);
+            let meridian_radius = a * (1.0 - e2) / w.powf(1.5);
+            let normal_radius = a / w.sqrt();
+            let areal = (d_lat.x * d_lon.y - d_lat.y * d_lon.x).abs() / (meridian_radius * normal_radius * lat.cos());
+            assert_approx_eq!(areal, 1.0, 1e-9);
+        }
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+        let albers = AlbersEqualArea::new(0.4, -1.7, 0.5, 0.8, 0.0, 0.0, &geo_ellipsoid::GeoEllipsoid::grs80());
+        let back: AlbersEqualArea = serde_json::from_str(&serde_json::to_string(&albers).unwrap()).unwrap();
+        assert_eq!(back, albers);
+    }
+}
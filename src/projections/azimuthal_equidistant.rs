@@ -0,0 +1,160 @@
+use na::Vector3;
+use std::f64;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::Projection;
+
+/// Ellipsoidal azimuthal equidistant projection centered on an arbitrary point
+/// 
+/// The distance of a projected point from the origin is the geodesic distance from the center
+/// and its direction is the geodesic azimuth at the center, both from Vincenty's formulas.
+/// Where Vincenty's inverse fails to converge, within a few tens of kilometers of the antipode,
+/// the great circle on the sphere of mean radius (2a + b) / 3 is used instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AzimuthalEquidistant {
+    center: Vector3<f64>,
+    semi_major_axis: f64,
+    inverse_flattening: f64
+}
+
+impl AzimuthalEquidistant {
+    /// Creates an azimuthal equidistant projection
+    /// 
+    /// # Arguments
+    /// 
+    /// * `center_lla` - Vector3 reference to the center (latitude, longitude, altitude) (radians, radians, meters), the altitude is ignored
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    pub fn new(center_lla: &Vector3<f64>, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> AzimuthalEquidistant {
+        AzimuthalEquidistant {
+            center: Vector3::new(center_lla.x, center_lla.y, 0.0),
+            semi_major_axis: ellipsoid.get_semi_major_axis(),
+            inverse_flattening: 1.0 / ellipsoid.get_flattening()
+        }
+    }
+
+    pub fn get_center(&self) -> Vector3<f64> {
+        self.center
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - x, y, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * (s, az) = geodesic distance and azimuth from the center
+    /// * x = s * sin(az), y = s * cos(az)
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let (distance, azimuth) = match geo::vincenty_inverse(&self.center, lla_vec, &self.ellipsoid()) {
+            Ok(geodesic) => (geodesic.distance, geodesic.initial_azimuth),
+            Err(_) => self.great_circle(lla_vec)
+        };
+        Vector3::new(distance * azimuth.sin(), distance * azimuth.cos(), lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (x, y, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * s = sqrt(x^2 + y^2), az = atan2(x, y)
+    /// * (lat, lon) = geodesic destination from the center after s along az
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let distance = proj_vec.x.hypot(proj_vec.y);
+        if distance == 0.0 {
+            return Vector3::new(self.center.x, self.center.y, proj_vec.z);
+        }
+        let destination = geo::vincenty_direct(&self.center, proj_vec.x.atan2(proj_vec.y), distance, &self.ellipsoid()).destination;
+        Vector3::new(destination.x, destination.y, proj_vec.z)
+    }
+
+    fn ellipsoid(&self) -> geo_ellipsoid::GeoEllipsoid {
+        geo_ellipsoid::GeoEllipsoid::new(self.semi_major_axis, self.inverse_flattening)
+    }
+
+    /// Great circle distance and azimuth from the center on the mean sphere
+    fn great_circle(&self, lla_vec: &Vector3<f64>) -> (f64, f64) {
+        let semi_minor_axis = self.semi_major_axis * (1.0 - 1.0 / self.inverse_flattening);
+        let radius = (2.0 * self.semi_major_axis + semi_minor_axis) / 3.0;
+        let (sin_lat1, cos_lat1) = self.center.x.sin_cos();
+        let (sin_lat2, cos_lat2) = lla_vec.x.sin_cos();
+        let dlon = lla_vec.y - self.center.y;
+        let y = cos_lat2 * dlon.sin();
+        let x = cos_lat1 * sin_lat2 - sin_lat1 * cos_lat2 * dlon.cos();
+        let angle = y.hypot(x).atan2(sin_lat1 * sin_lat2 + cos_lat1 * cos_lat2 * dlon.cos());
+        (radius * angle, y.atan2(x))
+    }
+}
+
+impl Projection for AzimuthalEquidistant {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.forward(lla_vec))
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let center = Vector3::new(45.0f64.to_radians(), 10.0f64.to_radians(), 0.0);
+        let aeqd = AzimuthalEquidistant::new(&center, &ellipsoid);
+        assert_eq!(aeqd.forward(&center), Vector3::new(0.0, 0.0, 0.0));
+        //The point 1000 km north along the meridian, from integrating the meridional radius
+        let north = aeqd.forward(&Vector3::new(53.991270373227f64.to_radians(), 10.0f64.to_radians(), 0.0));
+        assert_approx_eq!(north.x, 0.0, 1e-6);
+        assert_approx_eq!(north.y, 1000000.0, 1e-3);
+        //Geoscience Australia's Flinders Peak to Buninyong example on GRS80
+        let grs80 = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::GRS_1980_SEMI_MAJOR_AXIS_METERS,
+                                                     geo_ellipsoid::GRS_1980_FLATTENING);
+        let flinders = Vector3::new(-(37.0f64 + 57.0 / 60.0 + 3.72030 / 3600.0).to_radians(),
+                                    (144.0f64 + 25.0 / 60.0 + 29.52440 / 3600.0).to_radians(), 0.0);
+        let buninyong = Vector3::new(-(37.0f64 + 39.0 / 60.0 + 10.15610 / 3600.0).to_radians(),
+                                     (143.0f64 + 55.0 / 60.0 + 35.38390 / 3600.0).to_radians(), 0.0);
+        let proj_vec = AzimuthalEquidistant::new(&flinders, &grs80).forward(&buninyong);
+        assert_approx_eq!(proj_vec.x.hypot(proj_vec.y), 54972.271, 1e-3);
+        let azimuth = proj_vec.x.atan2(proj_vec.y).to_degrees() + 360.0;
+        assert_approx_eq!(azimuth, 306.0 + 52.0 / 60.0 + 5.37 / 3600.0, 1e-5);
+        //The antipode stays finite through the great circle fallback
+        let antipode = aeqd.forward(&Vector3::new(-45.0f64.to_radians(), -170.0f64.to_radians(), 0.0));
+        assert!(antipode.x.is_finite() && antipode.y.is_finite());
+        assert_approx_eq!(antipode.x.hypot(antipode.y), 2.0e7, 2.0e4);
+    }
+    #[test]
+    fn test_inverse() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let center = Vector3::new(-20.0f64.to_radians(), 140.0f64.to_radians(), 0.0);
+        let aeqd = AzimuthalEquidistant::new(&center, &ellipsoid);
+        assert_eq!(aeqd.inverse(&Vector3::new(0.0, 0.0, 4.0)), Vector3::new(center.x, center.y, 4.0));
+        for lat_deg in [-80.0f64, -45.0, -20.0, 0.0, 30.0, 70.0].iter() {
+            for lon_deg in [-170.0f64, 0.0, 100.0, 139.0, 150.0].iter() {
+                let lla_vec = Vector3::new(lat_deg.to_radians(), lon_deg.to_radians(), 0.0);
+                let back = aeqd.inverse(&aeqd.forward(&lla_vec));
+                assert_approx_eq!(back.x, lla_vec.x, 1e-11);
+                assert_approx_eq!(back.y, lla_vec.y, 1e-11);
+            }
+        }
+    }
+}
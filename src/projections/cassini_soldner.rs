@@ -0,0 +1,162 @@
+use na::Vector3;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::Projection;
+
+/// Ellipsoidal Cassini-Soldner projection
+/// 
+/// The series are truncated at the fifth power of the longitude difference, so accuracy degrades
+/// away from the central meridian. Round trips hold to a few millimeters 2 degrees from it, and
+/// the projection is meant for zones only a few degrees wide.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CassiniSoldner {
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64,
+    semi_major_axis: f64,
+    inverse_flattening: f64,
+    e2: f64,
+    origin_arc: f64
+}
+
+impl CassiniSoldner {
+    /// Creates a Cassini-Soldner projection
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat0` - latitude of natural origin (radians)
+    /// * `lon0` - longitude of natural origin (radians)
+    /// * `false_easting` - easting of the natural origin (ellipsoid units)
+    /// * `false_northing` - northing of the natural origin (ellipsoid units)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    pub fn new(lat0: f64, lon0: f64, false_easting: f64, false_northing: f64,
+               ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> CassiniSoldner {
+        CassiniSoldner {
+            lon0,
+            false_easting,
+            false_northing,
+            semi_major_axis: ellipsoid.get_semi_major_axis(),
+            inverse_flattening: 1.0 / ellipsoid.get_flattening(),
+            e2: ellipsoid.get_first_ecc_squared(),
+            origin_arc: geo::meridian_arc_length(lat0, ellipsoid)
+        }
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_false_easting(&self) -> f64 {
+        self.false_easting
+    }
+
+    pub fn get_false_northing(&self) -> f64 {
+        self.false_northing
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - easting, northing, altitude
+    /// 
+    /// # Formula
+    /// 
+    /// * A = (lon - lon0) * cos(lat), T = tan(lat)^2, C = e^2 * cos(lat)^2 / (1 - e^2)
+    /// * easting = FE + N * (A - T * A^3 / 6 - (8 - T + 8 * C) * T * A^5 / 120)
+    /// * northing = FN + M - M0 + N * tan(lat) * (A^2 / 2 + (5 - T + 6 * C) * A^4 / 24)
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let (sin_lat, cos_lat) = lla_vec.x.sin_cos();
+        let tan_lat = lla_vec.x.tan();
+        let nu = self.semi_major_axis / (1.0 - self.e2 * sin_lat.powi(2)).sqrt();
+        let a = geo::wrap_longitude(lla_vec.y - self.lon0) * cos_lat;
+        let t = tan_lat.powi(2);
+        let c = self.e2 * cos_lat.powi(2) / (1.0 - self.e2);
+        let x = a - t * a.powi(3) / 6.0 - (8.0 - t + 8.0 * c) * t * a.powi(5) / 120.0;
+        let z = geo::meridian_arc_length(lla_vec.x, &self.ellipsoid()) - self.origin_arc
+            + nu * tan_lat * (a.powi(2) / 2.0 + (5.0 - t + 6.0 * c) * a.powi(4) / 24.0);
+        Vector3::new(self.false_easting + nu * x, self.false_northing + z, lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (easting, northing, altitude)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * lat1 = footpoint latitude of M0 + northing - FN, D = (easting - FE) / N1, T1 = tan(lat1)^2
+    /// * lat = lat1 - (N1 * tan(lat1) / M1) * (D^2 / 2 - (1 + 3 * T1) * D^4 / 24)
+    /// * lon = lon0 + (D - T1 * D^3 / 3 + (1 + 3 * T1) * T1 * D^5 / 15) / cos(lat1)
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let lat1 = geo::latitude_from_meridian_arc(self.origin_arc + proj_vec.y - self.false_northing, &self.ellipsoid());
+        let (sin_lat1, cos_lat1) = lat1.sin_cos();
+        let tan_lat1 = lat1.tan();
+        let w = 1.0 - self.e2 * sin_lat1.powi(2);
+        let nu1 = self.semi_major_axis / w.sqrt();
+        let rho1 = self.semi_major_axis * (1.0 - self.e2) / w.powf(1.5);
+        let t1 = tan_lat1.powi(2);
+        let d = (proj_vec.x - self.false_easting) / nu1;
+        let lat = lat1 - nu1 * tan_lat1 / rho1 * (d.powi(2) / 2.0 - (1.0 + 3.0 * t1) * d.powi(4) / 24.0);
+        let dlon = (d - t1 * d.powi(3) / 3.0 + (1.0 + 3.0 * t1) * t1 * d.powi(5) / 15.0) / cos_lat1;
+        Vector3::new(lat, geo::wrap_longitude(self.lon0 + dlon), proj_vec.z)
+    }
+
+    fn ellipsoid(&self) -> geo_ellipsoid::GeoEllipsoid {
+        geo_ellipsoid::GeoEllipsoid::new(self.semi_major_axis, self.inverse_flattening)
+    }
+}
+
+impl Projection for CassiniSoldner {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.forward(lla_vec))
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        //EPSG guidance note 7-2 example, Trinidad 1903 / Trinidad Grid on Clarke 1858 in Clarke's links
+        let clarke = geo_ellipsoid::GeoEllipsoid::new(31706587.88, 294.2606764);
+        let trinidad = CassiniSoldner::new((10.0f64 + 26.0 / 60.0 + 30.0 / 3600.0).to_radians(),
+                                           -(61.0f64 + 20.0 / 60.0).to_radians(), 430000.00, 325000.00, &clarke);
+        let proj_vec = trinidad.forward(&Vector3::new(10.0f64.to_radians(), -62.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 66644.94, 0.01);
+        assert_approx_eq!(proj_vec.y, 82536.22, 0.01);
+        let back = trinidad.inverse(&Vector3::new(66644.94, 82536.22, 0.0));
+        assert_approx_eq!(back.x.to_degrees(), 10.0, 1e-7);
+        assert_approx_eq!(back.y.to_degrees(), -62.0, 1e-7);
+    }
+    #[test]
+    fn test_inverse() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let cassini = CassiniSoldner::new(0.9, 0.2, 40000.0, 80000.0, &ellipsoid);
+        //Round trip errors grow with the distance from the central meridian
+        for (dlon_deg, tolerance) in [(0.0f64, 1e-15), (0.5, 1e-11), (2.0, 1e-9)].iter() {
+            for lat_deg in [-60.0f64, -10.0, 0.0, 30.0, 51.5, 75.0].iter() {
+                let lla_vec = Vector3::new(lat_deg.to_radians(), 0.2 + dlon_deg.to_radians(), 0.0);
+                let back = cassini.inverse(&cassini.forward(&lla_vec));
+                assert_approx_eq!(back.x, lla_vec.x, *tolerance);
+                assert_approx_eq!(back.y, lla_vec.y, *tolerance);
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use na::Vector3;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::Projection;
+
+/// Spherical equirectangular (plate carree) projection with a standard parallel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Equirectangular {
+    lat_ts: f64,
+    lon0: f64,
+    radius: f64
+}
+
+impl Equirectangular {
+    /// Creates an equirectangular projection
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat_ts` - standard parallel of true scale (radians)
+    /// * `lon0` - central meridian (radians)
+    /// * `radius` - sphere radius (meters)
+    pub fn new(lat_ts: f64, lon0: f64, radius: f64) -> Equirectangular {
+        Equirectangular {
+            lat_ts,
+            lon0,
+            radius
+        }
+    }
+
+    /// Creates an equirectangular projection on the mean radius (2a + b) / 3 of an ellipsoid
+    pub fn from_ellipsoid(lat_ts: f64, lon0: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> Equirectangular {
+        let radius = ellipsoid.get_mean_radius();
+        Equirectangular::new(lat_ts, lon0, radius)
+    }
+
+    pub fn get_lat_ts(&self) -> f64 {
+        self.lat_ts
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - x, y, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * x = R * (lon - lon0) * cos(lat_ts)
+    /// * y = R * lat
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        Vector3::new(self.radius * geo::wrap_longitude(lla_vec.y - self.lon0) * self.lat_ts.cos(),
+                     self.radius * lla_vec.x,
+                     lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (x, y, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * lat = y / R
+    /// * lon = lon0 + x / (R * cos(lat_ts))
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        Vector3::new(proj_vec.y / self.radius,
+                     geo::wrap_longitude(self.lon0 + proj_vec.x / (self.radius * self.lat_ts.cos())),
+                     proj_vec.z)
+    }
+}
+
+impl Projection for Equirectangular {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.forward(lla_vec))
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        let plate_carree = Equirectangular::new(0.0, 0.0, geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS);
+        let proj_vec = plate_carree.forward(&Vector3::new(0.0, 1.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x / 1000.0, 111.319, 1e-3);
+        assert_approx_eq!(proj_vec.y, 0.0, 1e-12);
+        //A standard parallel at 60 degrees halves the longitude scale
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let mean = Equirectangular::from_ellipsoid(60.0f64.to_radians(), 0.0, &ellipsoid);
+        assert_approx_eq!(mean.get_radius(), 6371008.771, 1e-3);
+        let proj_vec = mean.forward(&Vector3::new(0.5, 1.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, mean.get_radius() * 1.0f64.to_radians() / 2.0, 1e-6);
+        assert_approx_eq!(proj_vec.y, mean.get_radius() * 0.5, 1e-9);
+    }
+    #[test]
+    fn test_inverse() {
+        let projection = Equirectangular::new(0.7, -0.3, 6371000.0);
+        let lla_vecs: Vec<Vector3<f64>> = [-1.5f64, -0.4, 0.0, 0.25, 1.2].iter()
+            .map(|lat| Vector3::new(*lat, lat * 1.5 - 0.3, 8.0)).collect();
+        let proj_vecs = projection.project_batch(&lla_vecs).unwrap();
+        let back = projection.unproject_batch(&proj_vecs).unwrap();
+        for (lla_vec, back_vec) in lla_vecs.iter().zip(back.iter()) {
+            assert_approx_eq!(back_vec.x, lla_vec.x, 1e-15);
+            assert_approx_eq!(back_vec.y, lla_vec.y, 1e-15);
+            assert_eq!(back_vec.z, 8.0);
+        }
+    }
+}
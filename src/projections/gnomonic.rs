@@ -0,0 +1,155 @@
+use na::Vector3;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::Projection;
+
+/// Spherical gnomonic projection, mapping great circles to straight lines
+/// 
+/// Only the hemisphere centered on the projection center is representable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gnomonic {
+    lat0: f64,
+    lon0: f64,
+    radius: f64
+}
+
+impl Gnomonic {
+    /// Creates a gnomonic projection on the mean Earth radius
+    /// 
+    /// # Arguments
+    /// 
+    /// * `center_lla` - Vector3 reference to the center (latitude, longitude, altitude) (radians, radians, meters), the altitude is ignored
+    pub fn new(center_lla: &Vector3<f64>) -> Gnomonic {
+        Gnomonic::with_radius(center_lla, geo_ellipsoid::MEAN_EARTH_RADIUS_METERS)
+    }
+
+    /// Creates a gnomonic projection on a sphere of the given radius (meters)
+    pub fn with_radius(center_lla: &Vector3<f64>, radius: f64) -> Gnomonic {
+        Gnomonic {
+            lat0: center_lla.x,
+            lon0: center_lla.y,
+            radius
+        }
+    }
+
+    pub fn get_center(&self) -> Vector3<f64> {
+        Vector3::new(self.lat0, self.lon0, 0.0)
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * Result<nalgebra::Vector3<f64>, GeoError> - x, y, altitude (meters), or OutsideProjectionDomain
+    ///   at or beyond 90 degrees from the center
+    /// 
+    /// # Formula
+    /// 
+    /// * cos(c) = sin(lat0) * sin(lat) + cos(lat0) * cos(lat) * cos(lon - lon0)
+    /// * x = R * cos(lat) * sin(lon - lon0) / cos(c)
+    /// * y = R * (cos(lat0) * sin(lat) - sin(lat0) * cos(lat) * cos(lon - lon0)) / cos(c)
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        let (sin_lat0, cos_lat0) = self.lat0.sin_cos();
+        let (sin_lat, cos_lat) = lla_vec.x.sin_cos();
+        let (sin_dlon, cos_dlon) = (lla_vec.y - self.lon0).sin_cos();
+        let cos_c = sin_lat0 * sin_lat + cos_lat0 * cos_lat * cos_dlon;
+        if cos_c <= 1e-12 {
+            return Err(GeoError::OutsideProjectionDomain);
+        }
+        Ok(Vector3::new(self.radius * cos_lat * sin_dlon / cos_c,
+                        self.radius * (cos_lat0 * sin_lat - sin_lat0 * cos_lat * cos_dlon) / cos_c,
+                        lla_vec.z))
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (x, y, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * rho = sqrt(x^2 + y^2), c = atan(rho / R)
+    /// * lat = asin(cos(c) * sin(lat0) + y * sin(c) * cos(lat0) / rho)
+    /// * lon = lon0 + atan2(x * sin(c), rho * cos(lat0) * cos(c) - y * sin(lat0) * sin(c))
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let rho = proj_vec.x.hypot(proj_vec.y);
+        if rho == 0.0 {
+            return Vector3::new(self.lat0, self.lon0, proj_vec.z);
+        }
+        let (sin_lat0, cos_lat0) = self.lat0.sin_cos();
+        let (sin_c, cos_c) = (rho / self.radius).atan().sin_cos();
+        let lat = (cos_c * sin_lat0 + proj_vec.y * sin_c * cos_lat0 / rho).clamp(-1.0, 1.0).asin();
+        let dlon = (proj_vec.x * sin_c).atan2(rho * cos_lat0 * cos_c - proj_vec.y * sin_lat0 * sin_c);
+        Vector3::new(lat, geo::wrap_longitude(self.lon0 + dlon), proj_vec.z)
+    }
+}
+
+impl Projection for Gnomonic {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        self.forward(lla_vec)
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        let center = Vector3::new(40.0f64.to_radians(), -30.0f64.to_radians(), 0.0);
+        let gnomonic = Gnomonic::new(&center);
+        assert_eq!(gnomonic.forward(&center).unwrap(), Vector3::new(0.0, 0.0, 0.0));
+        //Three points on a common great circle, from rotating one unit vector towards another
+        let to_unit = |lat: f64, lon: f64| Vector3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin());
+        let start = to_unit(35.0f64.to_radians(), -50.0f64.to_radians());
+        let end = to_unit(55.0f64.to_radians(), 0.0);
+        let normal = start.cross(&end).normalize();
+        let onward = normal.cross(&start);
+        let points: Vec<Vector3<f64>> = [0.0f64, 0.2, 0.55].iter().map(|angle| {
+            let unit = start * angle.cos() + onward * angle.sin();
+            gnomonic.forward(&Vector3::new(unit.z.asin(), unit.y.atan2(unit.x), 0.0)).unwrap()
+        }).collect();
+        let (d1, d2) = (points[1] - points[0], points[2] - points[0]);
+        let cross = (d1.x * d2.y - d1.y * d2.x) / (d1.norm() * d2.norm());
+        assert_approx_eq!(cross, 0.0, 1e-9);
+        //Undefined on and beyond the horizon of the center
+        let horizon = Vector3::new(-50.0f64.to_radians(), -30.0f64.to_radians(), 0.0);
+        assert_eq!(gnomonic.forward(&horizon), Err(GeoError::OutsideProjectionDomain));
+        assert_eq!(gnomonic.project(&Vector3::new(-40.0f64.to_radians(), 150.0f64.to_radians(), 0.0)),
+                   Err(GeoError::OutsideProjectionDomain));
+    }
+    #[test]
+    fn test_inverse() {
+        let center = Vector3::new(-10.0f64.to_radians(), 100.0f64.to_radians(), 0.0);
+        let gnomonic = Gnomonic::with_radius(&center, 6371000.0);
+        assert_eq!(gnomonic.inverse(&Vector3::new(0.0, 0.0, 1.0)), Vector3::new(center.x, center.y, 1.0));
+        for lat_deg in [-85.0f64, -40.0, -10.0, 0.0, 30.0, 60.0].iter() {
+            for lon_deg in [40.0f64, 80.0, 100.0, 130.0, 170.0].iter() {
+                let lla_vec = Vector3::new(lat_deg.to_radians(), lon_deg.to_radians(), 0.0);
+                if let Ok(proj_vec) = gnomonic.forward(&lla_vec) {
+                    let back = gnomonic.inverse(&proj_vec);
+                    assert_approx_eq!(back.x, lla_vec.x, 1e-12);
+                    assert_approx_eq!(back.y, lla_vec.y, 1e-12);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use na::Vector3;
+use std::f64;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::{Projection, conformal_t, conformal_t_inverse};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Constructor arguments of a Lambert conformal conic projection, its serialized form
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Definition {
+    /// Arguments of LambertConformalConic::new
+    TwoParallels {
+        lat0: f64,
+        lon0: f64,
+        sp1: f64,
+        sp2: f64,
+        false_easting: f64,
+        false_northing: f64,
+        ellipsoid: geo_ellipsoid::GeoEllipsoid
+    },
+    /// Arguments of LambertConformalConic::new_one_parallel
+    OneParallel {
+        lat0: f64,
+        lon0: f64,
+        scale_factor: f64,
+        false_easting: f64,
+        false_northing: f64,
+        ellipsoid: geo_ellipsoid::GeoEllipsoid
+    }
+}
+
+/// Ellipsoidal Lambert conformal conic projection with one or two standard parallels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LambertConformalConic {
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64,
+    ecc: f64,
+    cone_constant: f64,
+    radius_factor: f64,
+    origin_radius: f64,
+    definition: Definition
+}
+
+impl LambertConformalConic {
+    /// Creates a Lambert conformal conic projection with two standard parallels
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat0` - latitude of false origin (radians)
+    /// * `lon0` - longitude of false origin (radians)
+    /// * `sp1` - first standard parallel (radians)
+    /// * `sp2` - second standard parallel (radians)
+    /// * `false_easting` - easting of the false origin (meters)
+    /// * `false_northing` - northing of the false origin (meters)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// 
+    /// # Formula
+    /// 
+    /// * m = cos(lat) / sqrt(1 - e^2 * sin(lat)^2), t = tan(pi / 4 - lat / 2) / ((1 - e * sin(lat)) / (1 + e * sin(lat)))^(e / 2)
+    /// * n = (ln(m1) - ln(m2)) / (ln(t1) - ln(t2)), F = m1 / (n * t1^n)
+    /// 
+    /// Equal standard parallels reduce to the tangent cone, n = sin(sp1).
+    pub fn new(lat0: f64, lon0: f64, sp1: f64, sp2: f64, false_easting: f64, false_northing: f64,
+               ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> LambertConformalConic {
+        let ecc = ellipsoid.get_first_ecc();
+        let m = |lat: f64| lat.cos() / (1.0 - (ecc * lat.sin()).powi(2)).sqrt();
+        let (m1, t1) = (m(sp1), conformal_t(sp1, ecc));
+        let cone_constant = if (sp1 - sp2).abs() < 1e-12 {
+            sp1.sin()
+        } else {
+            (m1.ln() - m(sp2).ln()) / (t1.ln() - conformal_t(sp2, ecc).ln())
+        };
+        let radius_factor = ellipsoid.get_semi_major_axis() * m1 / (cone_constant * t1.powf(cone_constant));
+        LambertConformalConic {
+            lon0,
+            false_easting,
+            false_northing,
+            ecc,
+            cone_constant,
+            radius_factor,
+            origin_radius: radius_factor * conformal_t(lat0, ecc).powf(cone_constant),
+            definition: Definition::TwoParallels { lat0, lon0, sp1, sp2, false_easting, false_northing, ellipsoid: *ellipsoid }
+        }
+    }
+
+    /// Creates a Lambert conformal conic projection with a single standard parallel and a scale factor
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat0` - latitude of natural origin, the standard parallel (radians)
+    /// * `lon0` - longitude of natural origin (radians)
+    /// * `scale_factor` - scale factor on the standard parallel
+    /// * `false_easting` - easting of the natural origin (meters)
+    /// * `false_northing` - northing of the natural origin (meters)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// 
+    /// # Formula
+    /// 
+    /// * n = sin(lat0), F = m0 / (n * t0^n), radii scaled by k0
+    pub fn new_one_parallel(lat0: f64, lon0: f64, scale_factor: f64, false_easting: f64, false_northing: f64,
+                            ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> LambertConformalConic {
+        let mut lcc = LambertConformalConic::new(lat0, lon0, lat0, lat0, false_easting, false_northing, ellipsoid);
+        lcc.radius_factor *= scale_factor;
+        lcc.origin_radius *= scale_factor;
+        lcc.definition = Definition::OneParallel { lat0, lon0, scale_factor, false_easting, false_northing, ellipsoid: *ellipsoid };
+        lcc
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_false_easting(&self) -> f64 {
+        self.false_easting
+    }
+
+    pub fn get_false_northing(&self) -> f64 {
+        self.false_northing
+    }
+
+    /// Cone constant n
+    pub fn get_cone_constant(&self) -> f64 {
+        self.cone_constant
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - easting, northing, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * r = a * F * t^n, theta = n * (lon - lon0)
+    /// * easting = FE + r * sin(theta), northing = FN + r0 - r * cos(theta)
+    /// 
+    /// The pole opposite the apex of the cone has no finite image, Projection::project rejects it.
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let r = self.radius_factor * conformal_t(lla_vec.x, self.ecc).powf(self.cone_constant);
+        let theta = self.cone_constant * geo::wrap_longitude(lla_vec.y - self.lon0);
+        Vector3::new(self.false_easting + r * theta.sin(),
+                     self.false_northing + self.origin_radius - r * theta.cos(),
+                     lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (easting, northing, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * r = sign(n) * sqrt(dE^2 + (r0 - dN)^2), t = (r / (a * F))^(1 / n)
+    /// * theta = atan2(sign(n) * dE, sign(n) * (r0 - dN)), lon = theta / n + lon0
+    /// * lat = pi / 2 - 2 * atan(t * ((1 - e * sin(lat)) / (1 + e * sin(lat)))^(e / 2)), iterated
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let sign = self.cone_constant.signum();
+        let d_east = proj_vec.x - self.false_easting;
+        let d_north = self.origin_radius - (proj_vec.y - self.false_northing);
+        let r = sign * d_east.hypot(d_north);
+        let t = (r / self.radius_factor).powf(1.0 / self.cone_constant);
+        let theta = (sign * d_east).atan2(sign * d_north);
+        Vector3::new(conformal_t_inverse(t, self.ecc),
+                     geo::wrap_longitude(theta / self.cone_constant + self.lon0),
+                     proj_vec.z)
+    }
+}
+
+impl Projection for LambertConformalConic {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        //The pole opposite the apex has no finite image
+        if self.cone_constant.signum() * lla_vec.x <= 1e-12 - f64::consts::FRAC_PI_2 {
+            return Err(GeoError::OutsideProjectionDomain);
+        }
+        Ok(self.forward(lla_vec))
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+/// Serializes the constructor arguments only
+#[cfg(feature = "serde")]
+impl Serialize for LambertConformalConic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.definition.serialize(serializer)
+    }
+}
+
+/// Rebuilds the derived values through the constructor, the ellipsoid is validated by its own deserializer
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for LambertConformalConic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LambertConformalConic, D::Error> {
+        Ok(match Definition::deserialize(deserializer)? {
+            Definition::TwoParallels { lat0, lon0, sp1, sp2, false_easting, false_northing, ellipsoid } =>
+                LambertConformalConic::new(lat0, lon0, sp1, sp2, false_easting, false_northing, &ellipsoid),
+            Definition::OneParallel { lat0, lon0, scale_factor, false_easting, false_northing, ellipsoid } =>
+                LambertConformalConic::new_one_parallel(lat0, lon0, scale_factor, false_easting, false_northing, &ellipsoid)
+        })
+    }
+}
+
+/// Creates the Lambert-93 projection of the French RGF93 grid
+/// 
+/// # Return Value
+/// 
+/// * LambertConformalConic - standard parallels 44 N and 49 N, false origin 46.5 N 3 E at (700000, 6600000) on GRS80
+/// 
+/// RGF93 agrees with WGS84 to well under a meter, so WGS84 coordinates are used without a datum shift.
+pub fn lambert93() -> LambertConformalConic {
+    let grs80 = geo_ellipsoid::GeoEllipsoid::grs80();
+    LambertConformalConic::new(46.5f64.to_radians(), 3.0f64.to_radians(), 44.0f64.to_radians(), 49.0f64.to_radians(),
+                               700000.0, 6600000.0, &grs80)
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const US_SURVEY_FOOT_METERS: f64 = 1200.0 / 3937.0;
+    #[test]
+    fn test_forward() {
+        //EPSG guidance note 7-2 example, NAD27 / Texas South Central on the Clarke 1866 ellipsoid
+        let clarke = geo_ellipsoid::GeoEllipsoid::new(6378206.400, 294.97870);
+        let lcc = LambertConformalConic::new((27.0f64 + 50.0 / 60.0).to_radians(), -99.0f64.to_radians(),
+                                             (28.0f64 + 23.0 / 60.0).to_radians(), (30.0f64 + 17.0 / 60.0).to_radians(),
+                                             2000000.0 * US_SURVEY_FOOT_METERS, 0.0, &clarke);
+        let proj_vec = lcc.forward(&Vector3::new(28.5f64.to_radians(), -96.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x / US_SURVEY_FOOT_METERS, 2963503.91, 0.005);
+        assert_approx_eq!(proj_vec.y / US_SURVEY_FOOT_METERS, 254759.80, 0.005);
+        //EPSG guidance note 7-2 example, JAD69 / Jamaica National Grid with a single parallel
+        let lcc = LambertConformalConic::new_one_parallel(18.0f64.to_radians(), -77.0f64.to_radians(), 1.0,
+                                                          250000.0, 150000.0, &clarke);
+        let lat = 17.0f64 + 55.0 / 60.0 + 55.80 / 3600.0;
+        let lon = -(76.0f64 + 56.0 / 60.0 + 37.26 / 3600.0);
+        let proj_vec = lcc.forward(&Vector3::new(lat.to_radians(), lon.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 255966.58, 0.005);
+        assert_approx_eq!(proj_vec.y, 142493.51, 0.005);
+        assert_eq!(lcc.project(&Vector3::new(-f64::consts::FRAC_PI_2, 0.0, 0.0)), Err(GeoError::OutsideProjectionDomain));
+    }
+    #[test]
+    fn test_inverse() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        //Round trips for a northern cone and a southern cone
+        let cones = [LambertConformalConic::new(0.5, -1.7, 0.6, 0.8, 600000.0, 0.0, &ellipsoid),
+                     LambertConformalConic::new(-0.6, 2.3, -0.5, -0.7, 0.0, 10000000.0, &ellipsoid)];
+        for lcc in cones.iter() {
+            for lat_deg in [-70.0f64, -40.0, -10.0, 0.0, 20.0, 45.0, 80.0].iter() {
+                for dlon_deg in [-60.0f64, -5.0, 0.0, 12.0, 60.0].iter() {
+                    let lla_vec = Vector3::new(lat_deg.to_radians(), lcc.get_lon0() + dlon_deg.to_radians(), 20.0);
+                    let back = lcc.inverse(&lcc.forward(&lla_vec));
+                    assert_approx_eq!(back.x, lla_vec.x, 1e-12);
+                    assert_approx_eq!(back.y, geo::wrap_longitude(lla_vec.y), 1e-12);
+                    assert_eq!(back.z, 20.0);
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_lambert93() {
+        //IGN published Lambert-93 constants n = 0.7256077650, C = 11754255.426 m, Ys = 12655612.050 m
+        let lcc = lambert93();
+        assert_approx_eq!(lcc.get_cone_constant(), 0.725_607_765_0, 1e-10);
+        let pole = lcc.forward(&Vector3::new(f64::consts::FRAC_PI_2, 3.0f64.to_radians(), 0.0));
+        assert_approx_eq!(pole.x, 700000.0, 1e-6);
+        assert_approx_eq!(pole.y, 12655612.050, 0.01);
+        let equator = lcc.forward(&Vector3::new(0.0, 3.0f64.to_radians(), 0.0));
+        assert_approx_eq!(equator.y, 12655612.050 - 11754255.426, 0.01);
+        let origin = lcc.forward(&Vector3::new(46.5f64.to_radians(), 3.0f64.to_radians(), 0.0));
+        assert_approx_eq!(origin.x, 700000.0, 1e-6);
+        assert_approx_eq!(origin.y, 6600000.0, 1e-6);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+        let clarke = geo_ellipsoid::GeoEllipsoid::clarke_1866();
+        for lcc in [lambert93(), LambertConformalConic::new_one_parallel(0.3, -1.3, 1.0, 250000.0, 150000.0, &clarke)].iter() {
+            let back: LambertConformalConic = serde_json::from_str(&serde_json::to_string(lcc).unwrap()).unwrap();
+            assert_eq!(&back, lcc);
+        }
+        assert!(serde_json::from_str::<LambertConformalConic>(r#"{"OneParallel":{"lat0":0.3}}"#).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use na::Vector3;
+use std::f64;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::{Projection, isometric_latitude, isometric_latitude_inverse};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Latitude limit of the ellipsoidal Mercator forward projection, beyond which latitudes are clamped (degrees)
+pub const MERCATOR_MAX_LATITUDE_DEGREES: f64 = 89.5;
+
+/// Constructor arguments of a Mercator projection, its serialized form
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Definition {
+    lat_ts: f64,
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64,
+    ellipsoid: geo_ellipsoid::GeoEllipsoid
+}
+
+/// Ellipsoidal Mercator projection with a latitude of true scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mercator {
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64,
+    ecc: f64,
+    scaled_radius: f64,
+    definition: Definition
+}
+
+impl Mercator {
+    /// Creates a Mercator projection about the Greenwich meridian without false offsets
+    /// 
+    /// # Arguments
+    /// 
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// * `lat_ts` - latitude of true scale (radians), 0 for the equator
+    pub fn new(ellipsoid: &geo_ellipsoid::GeoEllipsoid, lat_ts: f64) -> Mercator {
+        Mercator::new_with_origin(ellipsoid, lat_ts, 0.0, 0.0, 0.0)
+    }
+
+    /// Creates a Mercator projection with a central meridian and false offsets
+    /// 
+    /// # Arguments
+    /// 
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// * `lat_ts` - latitude of true scale (radians), 0 for the equator
+    /// * `lon0` - central meridian (radians)
+    /// * `false_easting` - easting of the natural origin (meters)
+    /// * `false_northing` - northing of the natural origin (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * k0 = cos(lat_ts) / sqrt(1 - e^2 * sin(lat_ts)^2)
+    pub fn new_with_origin(ellipsoid: &geo_ellipsoid::GeoEllipsoid, lat_ts: f64, lon0: f64,
+                           false_easting: f64, false_northing: f64) -> Mercator {
+        let ecc = ellipsoid.get_first_ecc();
+        let scale_factor = lat_ts.cos() / (1.0 - (ecc * lat_ts.sin()).powi(2)).sqrt();
+        Mercator {
+            lon0,
+            false_easting,
+            false_northing,
+            ecc,
+            scaled_radius: ellipsoid.get_semi_major_axis() * scale_factor,
+            definition: Definition { lat_ts, lon0, false_easting, false_northing, ellipsoid: *ellipsoid }
+        }
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_false_easting(&self) -> f64 {
+        self.false_easting
+    }
+
+    pub fn get_false_northing(&self) -> f64 {
+        self.false_northing
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - easting, northing, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * psi = asinh(tan(lat)) - e * atanh(e * sin(lat)), the isometric latitude
+    /// * easting = FE + a * k0 * (lon - lon0), northing = FN + a * k0 * psi
+    /// 
+    /// Latitudes are clamped to +/- MERCATOR_MAX_LATITUDE_DEGREES, the poles lie at infinity.
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let max_lat = MERCATOR_MAX_LATITUDE_DEGREES.to_radians();
+        let lat = lla_vec.x.clamp(-max_lat, max_lat);
+        let psi = isometric_latitude(lat, self.ecc);
+        Vector3::new(self.false_easting + self.scaled_radius * geo::wrap_longitude(lla_vec.y - self.lon0),
+                     self.false_northing + self.scaled_radius * psi,
+                     lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (easting, northing, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * psi = (northing - FN) / (a * k0), lon = lon0 + (easting - FE) / (a * k0)
+    /// * lat by Newton's method on psi(lat), dpsi / dlat = (1 - e^2) / ((1 - e^2 * sin(lat)^2) * cos(lat))
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let psi = (proj_vec.y - self.false_northing) / self.scaled_radius;
+        Vector3::new(isometric_latitude_inverse(psi, self.ecc),
+                     geo::wrap_longitude(self.lon0 + (proj_vec.x - self.false_easting) / self.scaled_radius),
+                     proj_vec.z)
+    }
+}
+
+impl Projection for Mercator {
+    /// Fails at the poles, other latitudes follow the clamping of Mercator::forward
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        if lla_vec.x.abs() >= f64::consts::FRAC_PI_2 {
+            return Err(GeoError::OutsideProjectionDomain);
+        }
+        Ok(self.forward(lla_vec))
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+/// Serializes the constructor arguments only
+#[cfg(feature = "serde")]
+impl Serialize for Mercator {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.definition.serialize(serializer)
+    }
+}
+
+/// Rebuilds the derived values through the constructor, the ellipsoid is validated by its own deserializer
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Mercator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Mercator, D::Error> {
+        let definition = Definition::deserialize(deserializer)?;
+        Ok(Mercator::new_with_origin(&definition.ellipsoid, definition.lat_ts, definition.lon0,
+                                     definition.false_easting, definition.false_northing))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        //WGS 84 / World Mercator (EPSG:3395), as computed by PROJ
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let world = Mercator::new(&ellipsoid, 0.0);
+        let proj_vec = world.forward(&Vector3::new(45.0f64.to_radians(), 0.0, 0.0));
+        assert_approx_eq!(proj_vec.x, 0.0, 1e-9);
+        assert_approx_eq!(proj_vec.y, 5591295.92, 0.005);
+        let proj_vec = world.forward(&Vector3::new(-30.0f64.to_radians(), -120.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, -13358338.90, 0.005);
+        //EPSG guidance note 7-2 example, Pulkovo 1942 / Mercator Caspian Sea (variant B)
+        let krassowsky = geo_ellipsoid::GeoEllipsoid::new(6378245.0, 298.3);
+        let caspian = Mercator::new_with_origin(&krassowsky, 42.0f64.to_radians(), 51.0f64.to_radians(), 0.0, 0.0);
+        let proj_vec = caspian.forward(&Vector3::new(53.0f64.to_radians(), 53.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 165704.29, 0.005);
+        assert_approx_eq!(proj_vec.y, 5171848.07, 0.005);
+        //The poles are clamped by forward and rejected by project
+        let clamped = world.forward(&Vector3::new(f64::consts::FRAC_PI_2, 0.0, 0.0));
+        assert!(clamped.y.is_finite());
+        assert_eq!(clamped, world.forward(&Vector3::new(MERCATOR_MAX_LATITUDE_DEGREES.to_radians(), 0.0, 0.0)));
+        assert_eq!(world.project(&Vector3::new(-f64::consts::FRAC_PI_2, 0.0, 0.0)), Err(GeoError::OutsideProjectionDomain));
+    }
+    #[test]
+    fn test_inverse() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let mercator = Mercator::new_with_origin(&ellipsoid, 20.0f64.to_radians(), -1.0, 500000.0, 0.0);
+        for lat_deg in [-89.0f64, -60.0, -12.5, 0.0, 33.0, 70.0, 89.4].iter() {
+            let lat = lat_deg.to_radians();
+            let proj_vec = mercator.forward(&Vector3::new(lat, 0.3, 2.0));
+            let back = mercator.inverse(&proj_vec);
+            assert_approx_eq!(back.x, lat, 1e-12);
+            assert_approx_eq!(back.y, 0.3, 1e-12);
+            assert_eq!(back.z, 2.0);
+        }
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+        let mercator = Mercator::new_with_origin(&geo_ellipsoid::GeoEllipsoid::wgs84(), 0.3, -1.0, 500000.0, 0.0);
+        let back: Mercator = serde_json::from_str(&serde_json::to_string(&mercator).unwrap()).unwrap();
+        assert_eq!(back, mercator);
+    }
+}
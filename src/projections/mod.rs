@@ -0,0 +1,192 @@
+use na::Vector3;
+use geo::GeoError;
+use std::f64;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod transverse_mercator;
+mod lambert_conformal_conic;
+mod albers_equal_area;
+mod stereographic;
+mod azimuthal_equidistant;
+mod equirectangular;
+mod sinusoidal;
+mod mercator;
+mod gnomonic;
+mod orthographic;
+mod cassini_soldner;
+mod oblique_mercator;
+
+/// NAD83 State Plane Coordinate System zones
+pub mod state_plane;
+
+pub use self::transverse_mercator::TransverseMercator;
+pub use self::lambert_conformal_conic::{LambertConformalConic, lambert93};
+pub use self::albers_equal_area::AlbersEqualArea;
+pub use self::stereographic::Stereographic;
+pub use self::azimuthal_equidistant::AzimuthalEquidistant;
+pub use self::equirectangular::Equirectangular;
+pub use self::sinusoidal::Sinusoidal;
+pub use self::mercator::{Mercator, MERCATOR_MAX_LATITUDE_DEGREES};
+pub use self::gnomonic::Gnomonic;
+pub use self::orthographic::Orthographic;
+pub use self::cassini_soldner::CassiniSoldner;
+pub use self::oblique_mercator::{ObliqueMercator, ObliqueMercatorVariant};
+
+/// Common interface of the map projections
+/// 
+/// Projected coordinates are (easting, northing, altitude) in meters and geographic coordinates
+/// are (latitude, longitude, altitude) in radians, radians, meters. The altitude is passed through.
+pub trait Projection {
+    /// Projects 3-d LLA coordinates, failing where the projection is undefined
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError>;
+
+    /// Recovers 3-d LLA coordinates, failing outside the projected domain
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError>;
+
+    /// Projects a slice of LLA coordinates, stopping at the first failure
+    fn project_batch(&self, lla_vecs: &[Vector3<f64>]) -> Result<Vec<Vector3<f64>>, GeoError> {
+        lla_vecs.iter().map(|lla_vec| self.project(lla_vec)).collect()
+    }
+
+    /// Recovers a slice of LLA coordinates, stopping at the first failure
+    fn unproject_batch(&self, proj_vecs: &[Vector3<f64>]) -> Result<Vec<Vector3<f64>>, GeoError> {
+        proj_vecs.iter().map(|proj_vec| self.unproject(proj_vec)).collect()
+    }
+}
+
+/// A configured projection of one of the supported kinds, as returned by the zone presets and crs::from_proj_string
+/// 
+/// LongLat is the unprojected geographic system, its forward and inverse pass coordinates through.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProjectedCrs {
+    LongLat,
+    TransverseMercator(TransverseMercator),
+    LambertConformalConic(LambertConformalConic),
+    Mercator(Mercator),
+    AlbersEqualArea(AlbersEqualArea),
+    Stereographic(Stereographic)
+}
+
+impl ProjectedCrs {
+    /// Converts 3-d LLA coordinates to projected coordinates with the wrapped projection
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        match *self {
+            ProjectedCrs::LongLat => *lla_vec,
+            ProjectedCrs::TransverseMercator(ref projection) => projection.forward(lla_vec),
+            ProjectedCrs::LambertConformalConic(ref projection) => projection.forward(lla_vec),
+            ProjectedCrs::Mercator(ref projection) => projection.forward(lla_vec),
+            ProjectedCrs::AlbersEqualArea(ref projection) => projection.forward(lla_vec),
+            ProjectedCrs::Stereographic(ref projection) => projection.forward(lla_vec)
+        }
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates with the wrapped projection
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        match *self {
+            ProjectedCrs::LongLat => *proj_vec,
+            ProjectedCrs::TransverseMercator(ref projection) => projection.inverse(proj_vec),
+            ProjectedCrs::LambertConformalConic(ref projection) => projection.inverse(proj_vec),
+            ProjectedCrs::Mercator(ref projection) => projection.inverse(proj_vec),
+            ProjectedCrs::AlbersEqualArea(ref projection) => projection.inverse(proj_vec),
+            ProjectedCrs::Stereographic(ref projection) => projection.inverse(proj_vec)
+        }
+    }
+}
+
+impl Projection for ProjectedCrs {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        match *self {
+            ProjectedCrs::LongLat => Ok(*lla_vec),
+            ProjectedCrs::TransverseMercator(ref projection) => projection.project(lla_vec),
+            ProjectedCrs::LambertConformalConic(ref projection) => projection.project(lla_vec),
+            ProjectedCrs::Mercator(ref projection) => projection.project(lla_vec),
+            ProjectedCrs::AlbersEqualArea(ref projection) => projection.project(lla_vec),
+            ProjectedCrs::Stereographic(ref projection) => projection.project(lla_vec)
+        }
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        match *self {
+            ProjectedCrs::LongLat => Ok(*proj_vec),
+            ProjectedCrs::TransverseMercator(ref projection) => projection.unproject(proj_vec),
+            ProjectedCrs::LambertConformalConic(ref projection) => projection.unproject(proj_vec),
+            ProjectedCrs::Mercator(ref projection) => projection.unproject(proj_vec),
+            ProjectedCrs::AlbersEqualArea(ref projection) => projection.unproject(proj_vec),
+            ProjectedCrs::Stereographic(ref projection) => projection.unproject(proj_vec)
+        }
+    }
+}
+
+/// Isometric latitude, asinh(tan(lat)) - e * atanh(e * sin(lat)), of the conformal projections
+pub(crate) fn isometric_latitude(lat: f64, ecc: f64) -> f64 {
+    lat.tan().asinh() - ecc * (ecc * lat.sin()).atanh()
+}
+
+/// Latitude for an isometric latitude, by Newton's method starting from the spherical inverse
+pub(crate) fn isometric_latitude_inverse(psi: f64, ecc: f64) -> f64 {
+    if psi.is_infinite() {
+        return f64::consts::FRAC_PI_2.copysign(psi);
+    }
+    let e2 = ecc.powi(2);
+    let mut lat = psi.sinh().atan();
+    for _ in 0..15 {
+        let sin_lat = lat.sin();
+        let delta = (psi - isometric_latitude(lat, ecc)) * (1.0 - e2 * sin_lat.powi(2)) * lat.cos() / (1.0 - e2);
+        lat += delta;
+        if delta.abs() < 1e-15 {
+            break;
+        }
+    }
+    lat
+}
+
+/// Snyder's t, tan(pi / 4 - lat / 2) / ((1 - e * sin(lat)) / (1 + e * sin(lat)))^(e / 2), of the conformal projections
+fn conformal_t(lat: f64, ecc: f64) -> f64 {
+    let e_sin = ecc * lat.sin();
+    (f64::consts::FRAC_PI_4 - lat / 2.0).tan() / ((1.0 - e_sin) / (1.0 + e_sin)).powf(ecc / 2.0)
+}
+
+/// Latitude for Snyder's t, by fixed-point iteration of lat = pi / 2 - 2 * atan(t * ((1 - e * sin(lat)) / (1 + e * sin(lat)))^(e / 2))
+fn conformal_t_inverse(t: f64, ecc: f64) -> f64 {
+    let mut lat = f64::consts::FRAC_PI_2 - 2.0 * t.atan();
+    for _ in 0..15 {
+        let e_sin = ecc * lat.sin();
+        let next = f64::consts::FRAC_PI_2 - 2.0 * (t * ((1.0 - e_sin) / (1.0 + e_sin)).powf(ecc / 2.0)).atan();
+        let done = (next - lat).abs() < 1e-15;
+        lat = next;
+        if done {
+            break;
+        }
+    }
+    lat
+}
+
+/// Snyder's q, (1 - e^2) * (sin(lat) / (1 - e^2 * sin(lat)^2) - ln((1 - e * sin(lat)) / (1 + e * sin(lat))) / (2 * e)), of the equal-area projections
+pub(crate) fn authalic_q(lat: f64, ecc: f64) -> f64 {
+    let sin_lat = lat.sin();
+    if ecc == 0.0 {
+        return 2.0 * sin_lat;
+    }
+    let e_sin = ecc * sin_lat;
+    (1.0 - ecc.powi(2)) * (sin_lat / (1.0 - e_sin.powi(2)) - ((1.0 - e_sin) / (1.0 + e_sin)).ln() / (2.0 * ecc))
+}
+
+/// Latitude for Snyder's q, by Newton's method starting from the authalic latitude
+fn authalic_q_inverse(q: f64, ecc: f64) -> f64 {
+    let q_pole = authalic_q(f64::consts::FRAC_PI_2, ecc);
+    if q.abs() >= q_pole {
+        return f64::consts::FRAC_PI_2.copysign(q);
+    }
+    let mut lat = (q / q_pole).asin();
+    for _ in 0..15 {
+        let e_sin_sq = (ecc * lat.sin()).powi(2);
+        let delta = (1.0 - e_sin_sq).powi(2) / (2.0 * lat.cos()) * (q - authalic_q(lat, ecc)) / (1.0 - ecc.powi(2));
+        lat += delta;
+        if delta.abs() < 1e-15 {
+            break;
+        }
+    }
+    lat
+}
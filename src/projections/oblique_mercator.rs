@@ -0,0 +1,312 @@
+use na::Vector3;
+use std::f64;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::{Projection, conformal_t, conformal_t_inverse};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Origin of the false offsets of a Hotine oblique Mercator projection
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ObliqueMercatorVariant {
+    /// Offsets refer to the natural origin, where the initial line meets the aposphere's equator (EPSG method 9812, variant A)
+    NaturalOrigin,
+    /// Offsets refer to the projection center (EPSG method 9815, variant B)
+    Center
+}
+
+/// Constructor arguments of a Hotine oblique Mercator projection, its serialized form
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Definition {
+    lat_c: f64,
+    lon_c: f64,
+    azimuth: f64,
+    rectified_grid_angle: f64,
+    scale_factor: f64,
+    false_easting: f64,
+    false_northing: f64,
+    variant: ObliqueMercatorVariant,
+    ellipsoid: geo_ellipsoid::GeoEllipsoid
+}
+
+/// Ellipsoidal Hotine oblique Mercator projection, following EPSG guidance note 7-2
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObliqueMercator {
+    variant: ObliqueMercatorVariant,
+    false_easting: f64,
+    false_northing: f64,
+    ecc: f64,
+    lon0: f64,
+    gamma0: f64,
+    rectified_grid_angle: f64,
+    a: f64,
+    b: f64,
+    h: f64,
+    center_u: f64,
+    definition: Definition
+}
+
+impl ObliqueMercator {
+    /// Creates a Hotine oblique Mercator projection
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat_c` - latitude of the projection center (radians)
+    /// * `lon_c` - longitude of the projection center (radians)
+    /// * `azimuth` - azimuth of the initial line at the center (radians)
+    /// * `rectified_grid_angle` - angle from the rectified grid to the skew grid (radians)
+    /// * `scale_factor` - scale factor on the initial line
+    /// * `false_easting` - easting of the natural origin or of the center, by variant (meters)
+    /// * `false_northing` - northing of the natural origin or of the center, by variant (meters)
+    /// * `variant` - origin the false offsets refer to
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// 
+    /// # Formula
+    /// 
+    /// * B = sqrt(1 + e^2 * cos(lat_c)^4 / (1 - e^2)), A = a * B * k_c * sqrt(1 - e^2) / (1 - e^2 * sin(lat_c)^2)
+    /// * D = B * sqrt(1 - e^2) / (cos(lat_c) * sqrt(1 - e^2 * sin(lat_c)^2)), F = D + sign(lat_c) * sqrt(D^2 - 1), H = F * t_c^B
+    /// * gamma0 = asin(sin(azimuth) / D), lon0 = lon_c - asin((F - 1 / F) / 2 * tan(gamma0)) / B
+    /// * u_c = sign(lat_c) * (A / B) * atan(sqrt(D^2 - 1) / cos(azimuth))
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(lat_c: f64, lon_c: f64, azimuth: f64, rectified_grid_angle: f64, scale_factor: f64,
+               false_easting: f64, false_northing: f64, variant: ObliqueMercatorVariant,
+               ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> ObliqueMercator {
+        let ecc = ellipsoid.get_first_ecc();
+        let e2 = ecc.powi(2);
+        let (sin_lat_c, cos_lat_c) = lat_c.sin_cos();
+        let sign = if lat_c < 0.0 { -1.0 } else { 1.0 };
+        let b = (1.0 + e2 * cos_lat_c.powi(4) / (1.0 - e2)).sqrt();
+        let a = ellipsoid.get_semi_major_axis() * b * scale_factor * (1.0 - e2).sqrt() / (1.0 - e2 * sin_lat_c.powi(2));
+        let d = (b * (1.0 - e2).sqrt() / (cos_lat_c * (1.0 - e2 * sin_lat_c.powi(2)).sqrt())).max(1.0);
+        let f = d + sign * (d.powi(2) - 1.0).sqrt();
+        let h = f * conformal_t(lat_c, ecc).powf(b);
+        let g = (f - 1.0 / f) / 2.0;
+        let gamma0 = (azimuth.sin() / d).asin();
+        //With the initial line due east or west the asin argument is +/- 1, where rounding would cost centimeters
+        let right_angle = (azimuth.abs() - f64::consts::FRAC_PI_2).abs() < 1e-12;
+        let lon0 = if right_angle {
+            lon_c - (g * gamma0.tan()).signum() * f64::consts::FRAC_PI_2 / b
+        } else {
+            lon_c - (g * gamma0.tan()).clamp(-1.0, 1.0).asin() / b
+        };
+        let center_u = if right_angle {
+            a * (lon_c - lon0)
+        } else {
+            sign * (a / b) * ((d.powi(2) - 1.0).sqrt() / azimuth.cos()).atan()
+        };
+        ObliqueMercator {
+            variant,
+            false_easting,
+            false_northing,
+            ecc,
+            lon0,
+            gamma0,
+            rectified_grid_angle,
+            a,
+            b,
+            h,
+            center_u,
+            definition: Definition {
+                lat_c,
+                lon_c,
+                azimuth,
+                rectified_grid_angle,
+                scale_factor,
+                false_easting,
+                false_northing,
+                variant,
+                ellipsoid: *ellipsoid
+            }
+        }
+    }
+
+    pub fn get_variant(&self) -> ObliqueMercatorVariant {
+        self.variant
+    }
+
+    pub fn get_false_easting(&self) -> f64 {
+        self.false_easting
+    }
+
+    pub fn get_false_northing(&self) -> f64 {
+        self.false_northing
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - easting, northing, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * Q = H / t^B, S = (Q - 1 / Q) / 2, T = (Q + 1 / Q) / 2, V = sin(B * (lon - lon0))
+    /// * U = (-V * cos(gamma0) + S * sin(gamma0)) / T, v = A * ln((1 - U) / (1 + U)) / (2 * B)
+    /// * u = A * atan2(S * cos(gamma0) + V * sin(gamma0), cos(B * (lon - lon0))) / B, less u_c for the center variant
+    /// * easting = v * cos(gamma_c) + u * sin(gamma_c) + FE, northing = u * cos(gamma_c) - v * sin(gamma_c) + FN
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let (sin_gamma0, cos_gamma0) = self.gamma0.sin_cos();
+        let dlon = geo::wrap_longitude(lla_vec.y - self.lon0);
+        let q = self.h / conformal_t(lla_vec.x, self.ecc).powf(self.b);
+        let s = (q - 1.0 / q) / 2.0;
+        let t = (q + 1.0 / q) / 2.0;
+        let v_term = (self.b * dlon).sin();
+        let u_term = (-v_term * cos_gamma0 + s * sin_gamma0) / t;
+        let v = self.a * ((1.0 - u_term) / (1.0 + u_term)).ln() / (2.0 * self.b);
+        let mut u = self.a * (s * cos_gamma0 + v_term * sin_gamma0).atan2((self.b * dlon).cos()) / self.b;
+        if self.variant == ObliqueMercatorVariant::Center {
+            u -= self.center_u;
+        }
+        let (sin_gamma_c, cos_gamma_c) = self.rectified_grid_angle.sin_cos();
+        Vector3::new(v * cos_gamma_c + u * sin_gamma_c + self.false_easting,
+                     u * cos_gamma_c - v * sin_gamma_c + self.false_northing,
+                     lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (easting, northing, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * v = dE * cos(gamma_c) - dN * sin(gamma_c), u = dN * cos(gamma_c) + dE * sin(gamma_c), plus u_c for the center variant
+    /// * Q = e^(-B * v / A), S = (Q - 1 / Q) / 2, T = (Q + 1 / Q) / 2, V = sin(B * u / A), U = (V * cos(gamma0) + S * sin(gamma0)) / T
+    /// * t = (H / sqrt((1 + U) / (1 - U)))^(1 / B), lat iterated from t
+    /// * lon = lon0 - atan2(S * cos(gamma0) - V * sin(gamma0), cos(B * u / A)) / B
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let (sin_gamma0, cos_gamma0) = self.gamma0.sin_cos();
+        let (sin_gamma_c, cos_gamma_c) = self.rectified_grid_angle.sin_cos();
+        let d_east = proj_vec.x - self.false_easting;
+        let d_north = proj_vec.y - self.false_northing;
+        let v = d_east * cos_gamma_c - d_north * sin_gamma_c;
+        let mut u = d_north * cos_gamma_c + d_east * sin_gamma_c;
+        if self.variant == ObliqueMercatorVariant::Center {
+            u += self.center_u;
+        }
+        let q = (-self.b * v / self.a).exp();
+        let s = (q - 1.0 / q) / 2.0;
+        let t = (q + 1.0 / q) / 2.0;
+        let v_term = (self.b * u / self.a).sin();
+        let u_term = (v_term * cos_gamma0 + s * sin_gamma0) / t;
+        let lat = if (u_term.abs() - 1.0).abs() < 1e-15 {
+            f64::consts::FRAC_PI_2.copysign(u_term)
+        } else {
+            conformal_t_inverse((self.h / ((1.0 + u_term) / (1.0 - u_term)).sqrt()).powf(1.0 / self.b), self.ecc)
+        };
+        let dlon = -(s * cos_gamma0 - v_term * sin_gamma0).atan2((self.b * u / self.a).cos()) / self.b;
+        Vector3::new(lat, geo::wrap_longitude(self.lon0 + dlon), proj_vec.z)
+    }
+}
+
+impl Projection for ObliqueMercator {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        let proj_vec = self.forward(lla_vec);
+        //The poles of the aposphere lie at infinity
+        if proj_vec.x.is_finite() && proj_vec.y.is_finite() {
+            Ok(proj_vec)
+        } else {
+            Err(GeoError::OutsideProjectionDomain)
+        }
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+/// Serializes the constructor arguments only
+#[cfg(feature = "serde")]
+impl Serialize for ObliqueMercator {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.definition.serialize(serializer)
+    }
+}
+
+/// Rebuilds the derived values through the constructor, the ellipsoid is validated by its own deserializer
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ObliqueMercator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ObliqueMercator, D::Error> {
+        let definition = Definition::deserialize(deserializer)?;
+        Ok(ObliqueMercator::new(definition.lat_c, definition.lon_c, definition.azimuth, definition.rectified_grid_angle,
+                                definition.scale_factor, definition.false_easting, definition.false_northing,
+                                definition.variant, &definition.ellipsoid))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn rso_borneo(variant: ObliqueMercatorVariant) -> ObliqueMercator {
+        let everest = geo_ellipsoid::GeoEllipsoid::new(6377298.556, 300.8017);
+        let (false_easting, false_northing) = match variant {
+            ObliqueMercatorVariant::NaturalOrigin => (0.0, 0.0),
+            ObliqueMercatorVariant::Center => (590476.87, 442857.65)
+        };
+        ObliqueMercator::new(4.0f64.to_radians(), 115.0f64.to_radians(),
+                             (53.0f64 + 18.0 / 60.0 + 56.9537 / 3600.0).to_radians(),
+                             (53.0f64 + 7.0 / 60.0 + 48.3685 / 3600.0).to_radians(),
+                             0.99984, false_easting, false_northing, variant, &everest)
+    }
+    #[test]
+    fn test_forward() {
+        //EPSG guidance note 7-2 example, Timbalai 1948 / RSO Borneo on Everest 1830 (1967 definition)
+        let point = Vector3::new((5.0f64 + 23.0 / 60.0 + 14.1129 / 3600.0).to_radians(),
+                                 (115.0f64 + 48.0 / 60.0 + 19.8196 / 3600.0).to_radians(), 0.0);
+        let proj_vec = rso_borneo(ObliqueMercatorVariant::Center).forward(&point);
+        assert_approx_eq!(proj_vec.x, 679245.73, 0.005);
+        assert_approx_eq!(proj_vec.y, 596562.78, 0.005);
+        //Both variants describe the same grid, the natural origin variant has zero offsets here
+        let natural = rso_borneo(ObliqueMercatorVariant::NaturalOrigin).forward(&point);
+        let center = rso_borneo(ObliqueMercatorVariant::NaturalOrigin).forward(&Vector3::new(4.0f64.to_radians(), 115.0f64.to_radians(), 0.0));
+        assert_approx_eq!(natural.x - center.x, proj_vec.x - 590476.87, 1e-6);
+        assert_approx_eq!(natural.y - center.y, proj_vec.y - 442857.65, 1e-6);
+        //The center variant puts the projection center at its offsets
+        let center = rso_borneo(ObliqueMercatorVariant::Center).forward(&Vector3::new(4.0f64.to_radians(), 115.0f64.to_radians(), 0.0));
+        assert_approx_eq!(center.x, 590476.87, 1e-6);
+        assert_approx_eq!(center.y, 442857.65, 1e-6);
+    }
+    #[test]
+    fn test_inverse() {
+        let rso = rso_borneo(ObliqueMercatorVariant::Center);
+        let back = rso.inverse(&Vector3::new(679245.73, 596562.78, 0.0));
+        assert_approx_eq!(back.x.to_degrees(), 5.0 + 23.0 / 60.0 + 14.1129 / 3600.0, 1e-7);
+        assert_approx_eq!(back.y.to_degrees(), 115.0 + 48.0 / 60.0 + 19.8196 / 3600.0, 1e-7);
+        for variant in [ObliqueMercatorVariant::NaturalOrigin, ObliqueMercatorVariant::Center].iter() {
+            let projection = rso_borneo(*variant);
+            for lat_deg in [-10.0f64, 0.0, 4.0, 7.5, 20.0].iter() {
+                for lon_deg in [105.0f64, 112.0, 115.0, 119.0, 125.0].iter() {
+                    let lla_vec = Vector3::new(lat_deg.to_radians(), lon_deg.to_radians(), 0.0);
+                    let back = projection.inverse(&projection.forward(&lla_vec));
+                    assert_approx_eq!(back.x, lla_vec.x, 1e-12);
+                    assert_approx_eq!(back.y, lla_vec.y, 1e-12);
+                }
+            }
+        }
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+        let wgs84 = geo_ellipsoid::GeoEllipsoid::wgs84();
+        for variant in [ObliqueMercatorVariant::NaturalOrigin, ObliqueMercatorVariant::Center].iter() {
+            let projection = ObliqueMercator::new(0.07, 2.05, 0.93, 0.94, 0.99984, 590476.87, 442857.65, *variant, &wgs84);
+            let back: ObliqueMercator = serde_json::from_str(&serde_json::to_string(&projection).unwrap()).unwrap();
+            assert_eq!(back, projection);
+        }
+        assert_eq!(serde_json::to_string(&ObliqueMercatorVariant::Center).unwrap(), r#""Center""#);
+    }
+}
@@ -0,0 +1,154 @@
+use na::Vector3;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::Projection;
+
+/// Spherical orthographic projection, the view of the globe from infinitely far away
+/// 
+/// Only the hemisphere facing the viewer is visible and the map is the disk of radius R.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orthographic {
+    lat0: f64,
+    lon0: f64,
+    radius: f64
+}
+
+impl Orthographic {
+    /// Creates an orthographic projection on the mean Earth radius
+    /// 
+    /// # Arguments
+    /// 
+    /// * `center_lla` - Vector3 reference to the center (latitude, longitude, altitude) (radians, radians, meters), the altitude is ignored
+    pub fn new(center_lla: &Vector3<f64>) -> Orthographic {
+        Orthographic::with_radius(center_lla, geo_ellipsoid::MEAN_EARTH_RADIUS_METERS)
+    }
+
+    /// Creates an orthographic projection on a sphere of the given radius (meters)
+    pub fn with_radius(center_lla: &Vector3<f64>, radius: f64) -> Orthographic {
+        Orthographic {
+            lat0: center_lla.x,
+            lon0: center_lla.y,
+            radius
+        }
+    }
+
+    pub fn get_center(&self) -> Vector3<f64> {
+        Vector3::new(self.lat0, self.lon0, 0.0)
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * Result<nalgebra::Vector3<f64>, GeoError> - x, y, altitude (meters), or OutsideProjectionDomain
+    ///   on the far hemisphere
+    /// 
+    /// # Formula
+    /// 
+    /// * visible where cos(c) = sin(lat0) * sin(lat) + cos(lat0) * cos(lat) * cos(lon - lon0) >= 0
+    /// * x = R * cos(lat) * sin(lon - lon0)
+    /// * y = R * (cos(lat0) * sin(lat) - sin(lat0) * cos(lat) * cos(lon - lon0))
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        let (sin_lat0, cos_lat0) = self.lat0.sin_cos();
+        let (sin_lat, cos_lat) = lla_vec.x.sin_cos();
+        let (sin_dlon, cos_dlon) = (lla_vec.y - self.lon0).sin_cos();
+        if sin_lat0 * sin_lat + cos_lat0 * cos_lat * cos_dlon < 0.0 {
+            return Err(GeoError::OutsideProjectionDomain);
+        }
+        Ok(Vector3::new(self.radius * cos_lat * sin_dlon,
+                        self.radius * (cos_lat0 * sin_lat - sin_lat0 * cos_lat * cos_dlon),
+                        lla_vec.z))
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (x, y, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * Result<nalgebra::Vector3<f64>, GeoError> - lat, long, alt (radians, radians, meters), or
+    ///   OutsideProjectionDomain outside the disk of radius R
+    /// 
+    /// # Formula
+    /// 
+    /// * rho = sqrt(x^2 + y^2), c = asin(rho / R)
+    /// * lat = asin(cos(c) * sin(lat0) + y * sin(c) * cos(lat0) / rho)
+    /// * lon = lon0 + atan2(x * sin(c), rho * cos(lat0) * cos(c) - y * sin(lat0) * sin(c))
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        let rho = proj_vec.x.hypot(proj_vec.y);
+        if rho > self.radius {
+            return Err(GeoError::OutsideProjectionDomain);
+        }
+        if rho == 0.0 {
+            return Ok(Vector3::new(self.lat0, self.lon0, proj_vec.z));
+        }
+        let (sin_lat0, cos_lat0) = self.lat0.sin_cos();
+        let sin_c = rho / self.radius;
+        let cos_c = (1.0 - sin_c.powi(2)).sqrt();
+        let lat = (cos_c * sin_lat0 + proj_vec.y * sin_c * cos_lat0 / rho).clamp(-1.0, 1.0).asin();
+        let dlon = (proj_vec.x * sin_c).atan2(rho * cos_lat0 * cos_c - proj_vec.y * sin_lat0 * sin_c);
+        Ok(Vector3::new(lat, geo::wrap_longitude(self.lon0 + dlon), proj_vec.z))
+    }
+}
+
+impl Projection for Orthographic {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        self.forward(lla_vec)
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        self.inverse(proj_vec)
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        let center = Vector3::new(50.0f64.to_radians(), 10.0f64.to_radians(), 0.0);
+        let orthographic = Orthographic::new(&center);
+        assert_eq!(orthographic.forward(&center).unwrap(), Vector3::new(0.0, 0.0, 0.0));
+        //Points 90 degrees away lie on the bounding circle
+        for lla_vec in [Vector3::new(-40.0f64.to_radians(), 10.0f64.to_radians(), 0.0),
+                        Vector3::new(0.0, 100.0f64.to_radians(), 0.0),
+                        Vector3::new(0.0, -80.0f64.to_radians(), 0.0)].iter() {
+            let proj_vec = orthographic.forward(lla_vec).unwrap();
+            assert_approx_eq!(proj_vec.x.hypot(proj_vec.y), geo_ellipsoid::MEAN_EARTH_RADIUS_METERS, 1e-6);
+        }
+        let far_side = Vector3::new(-50.0f64.to_radians(), -170.0f64.to_radians(), 0.0);
+        assert_eq!(orthographic.forward(&far_side), Err(GeoError::OutsideProjectionDomain));
+    }
+    #[test]
+    fn test_inverse() {
+        let center = Vector3::new(-25.0f64.to_radians(), 135.0f64.to_radians(), 0.0);
+        let orthographic = Orthographic::with_radius(&center, 6371000.0);
+        assert_eq!(orthographic.inverse(&Vector3::new(0.0, 0.0, 2.0)).unwrap(), Vector3::new(center.x, center.y, 2.0));
+        for lat_deg in [-80.0f64, -50.0, -25.0, 0.0, 20.0, 60.0].iter() {
+            for lon_deg in [60.0f64, 100.0, 135.0, 170.0, -150.0].iter() {
+                let lla_vec = Vector3::new(lat_deg.to_radians(), lon_deg.to_radians(), 0.0);
+                //Round trips hold across the visible hemisphere, away from the ambiguous rim
+                let proj_vec = match orthographic.forward(&lla_vec) {
+                    Ok(proj_vec) if proj_vec.x.hypot(proj_vec.y) < 0.999 * 6371000.0 => proj_vec,
+                    _ => continue
+                };
+                let back = orthographic.inverse(&proj_vec).unwrap();
+                assert_approx_eq!(back.x, lla_vec.x, 1e-10);
+                assert_approx_eq!(back.y, lla_vec.y, 1e-10);
+            }
+        }
+        assert_eq!(orthographic.inverse(&Vector3::new(5000000.0, 5000000.0, 0.0)), Err(GeoError::OutsideProjectionDomain));
+    }
+}
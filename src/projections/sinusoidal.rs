@@ -0,0 +1,151 @@
+use na::Vector3;
+use std::f64;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::{Projection, authalic_q};
+
+/// Spherical sinusoidal equal-area projection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sinusoidal {
+    lon0: f64,
+    radius: f64
+}
+
+impl Sinusoidal {
+    /// Creates a sinusoidal projection
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lon0` - central meridian (radians)
+    /// * `radius` - sphere radius (meters), MODIS grids use 6371007.181
+    pub fn new(lon0: f64, radius: f64) -> Sinusoidal {
+        Sinusoidal {
+            lon0,
+            radius
+        }
+    }
+
+    /// Creates a sinusoidal projection on the authalic radius of an ellipsoid
+    /// 
+    /// # Formula
+    /// 
+    /// * R = a * sqrt(q_p / 2), q_p = authalic q at the pole
+    pub fn from_ellipsoid(lon0: f64, ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> Sinusoidal {
+        let q_pole = authalic_q(f64::consts::FRAC_PI_2, ellipsoid.get_first_ecc());
+        Sinusoidal::new(lon0, ellipsoid.get_semi_major_axis() * (q_pole / 2.0).sqrt())
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - x, y, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * x = R * (lon - lon0) * cos(lat), with lon - lon0 wrapped to (-pi, pi]
+    /// * y = R * lat
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        Vector3::new(self.radius * geo::wrap_longitude(lla_vec.y - self.lon0) * lla_vec.x.cos(),
+                     self.radius * lla_vec.x,
+                     lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (x, y, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * lat = y / R
+    /// * lon = lon0 + x / (R * cos(lat)), lon0 at the poles
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let lat = proj_vec.y / self.radius;
+        let cos_lat = lat.cos();
+        let lon = if cos_lat.abs() < 1e-15 { self.lon0 } else { self.lon0 + proj_vec.x / (self.radius * cos_lat) };
+        Vector3::new(lat, geo::wrap_longitude(lon), proj_vec.z)
+    }
+}
+
+impl Projection for Sinusoidal {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.forward(lla_vec))
+    }
+
+    /// Fails outside the map outline, beyond the poles or more than 180 degrees from the central meridian
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        let lat = proj_vec.y / self.radius;
+        if lat.abs() > f64::consts::FRAC_PI_2 || proj_vec.x.abs() > self.radius * f64::consts::PI * lat.cos() * (1.0 + 1e-12) {
+            return Err(GeoError::OutsideProjectionDomain);
+        }
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const MODIS_RADIUS_METERS: f64 = 6371007.181;
+    const MODIS_TILE_METERS: f64 = 1111950.5197665;
+    #[test]
+    fn test_forward() {
+        let modis = Sinusoidal::new(0.0, MODIS_RADIUS_METERS);
+        //Upper left and lower right corners of MODIS tile h18v04, 50 N 0 E and 40 N 13.05 E, in a
+        //grid of 36 x 18 tiles centered on the origin
+        let x_min = (18.0 - 18.0) * MODIS_TILE_METERS;
+        let y_max = (9.0 - 4.0) * MODIS_TILE_METERS;
+        let upper_left = modis.forward(&Vector3::new(50.0f64.to_radians(), 0.0, 0.0));
+        assert_approx_eq!(upper_left.x, x_min, 1.0);
+        assert_approx_eq!(upper_left.y, y_max, 1.0);
+        let lower_right = modis.forward(&Vector3::new(40.0f64.to_radians(), (10.0 / 40.0f64.to_radians().cos()).to_radians(), 0.0));
+        assert_approx_eq!(lower_right.x, x_min + MODIS_TILE_METERS, 1.0);
+        assert_approx_eq!(lower_right.y, y_max - MODIS_TILE_METERS, 1.0);
+        //The poles collapse to points and both sides of the antimeridian reach the map edge
+        assert_approx_eq!(modis.forward(&Vector3::new(f64::consts::FRAC_PI_2, 2.0, 0.0)).x, 0.0, 1e-9);
+        let east_edge = modis.forward(&Vector3::new(0.0, f64::consts::PI, 0.0));
+        assert_approx_eq!(east_edge.x, MODIS_RADIUS_METERS * f64::consts::PI, 1e-6);
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        assert_approx_eq!(Sinusoidal::from_ellipsoid(0.0, &ellipsoid).get_radius(), 6371007.181, 1e-3);
+    }
+    #[test]
+    fn test_inverse() {
+        let modis = Sinusoidal::new(0.0, MODIS_RADIUS_METERS);
+        let upper_left = modis.inverse(&Vector3::new(0.0, 5.0 * MODIS_TILE_METERS, 0.0));
+        assert_approx_eq!(upper_left.x.to_degrees(), 50.0, 1e-7);
+        assert_approx_eq!(upper_left.y.to_degrees(), 0.0, 1e-12);
+        let pole = modis.inverse(&Vector3::new(0.0, MODIS_RADIUS_METERS * f64::consts::FRAC_PI_2, 0.0));
+        assert_eq!(pole.y, 0.0);
+        let projection = Sinusoidal::new(2.5, 6371000.0);
+        for lat_deg in [-89.0f64, -45.0, 0.0, 30.0, 89.9].iter() {
+            for dlon_deg in [-179.999f64, -60.0, 0.0, 120.0, 180.0].iter() {
+                let lla_vec = Vector3::new(lat_deg.to_radians(), geo::wrap_longitude(2.5 + dlon_deg.to_radians()), 0.0);
+                let back = projection.unproject(&projection.forward(&lla_vec)).unwrap();
+                assert_approx_eq!(back.x, lla_vec.x, 1e-12);
+                assert_approx_eq!(back.y, lla_vec.y, 1e-10);
+            }
+        }
+        assert_eq!(projection.unproject(&Vector3::new(2.1e7, 0.0, 0.0)), Err(GeoError::OutsideProjectionDomain));
+        assert_eq!(projection.unproject(&Vector3::new(0.0, 1.1e7, 0.0)), Err(GeoError::OutsideProjectionDomain));
+    }
+}
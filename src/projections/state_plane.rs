@@ -0,0 +1,289 @@
+use na::Vector3;
+use structs::geo_ellipsoid;
+use projections::{ProjectedCrs, TransverseMercator, LambertConformalConic};
+
+/// Projection method and method-specific parameters of a zone
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ZoneMethod {
+    /// Transverse Mercator with its central meridian scale factor
+    TransverseMercator(f64),
+    /// Lambert conformal conic with its two standard parallels (degrees)
+    LambertConformalConic(f64, f64)
+}
+
+/// Defining parameters of a NAD83 zone, angles in degrees and offsets in meters
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ZoneDefinition {
+    code: u16,
+    method: ZoneMethod,
+    lat0: f64,
+    lon0: f64,
+    false_easting: f64,
+    false_northing: f64
+}
+
+/// Degrees and minutes to degrees, the sign of the degrees applies to both
+const fn dm(degrees: f64, minutes: f64) -> f64 {
+    if degrees < 0.0 { degrees - minutes / 60.0 } else { degrees + minutes / 60.0 }
+}
+
+const fn tm(code: u16, lat0: f64, lon0: f64, scale_factor: f64, false_easting: f64, false_northing: f64) -> ZoneDefinition {
+    ZoneDefinition { code, method: ZoneMethod::TransverseMercator(scale_factor), lat0, lon0, false_easting, false_northing }
+}
+
+const fn lcc(code: u16, sp1: f64, sp2: f64, lat0: f64, lon0: f64, false_easting: f64, false_northing: f64) -> ZoneDefinition {
+    ZoneDefinition { code, method: ZoneMethod::LambertConformalConic(sp1, sp2), lat0, lon0, false_easting, false_northing }
+}
+
+/// NAD83 zones by FIPS code, from NOAA Manual NOS NGS 5. The oblique Alaska zone 5001 is not included.
+const ZONES: [ZoneDefinition; 123] = [
+    tm(101, dm(30.0, 30.0), dm(-85.0, 50.0), 0.999_96, 200000.0, 0.0),
+    tm(102, 30.0, dm(-87.0, 30.0), 0.999_933_333, 600000.0, 0.0),
+    tm(201, 31.0, dm(-110.0, 10.0), 0.9999, 213360.0, 0.0),
+    tm(202, 31.0, dm(-111.0, 55.0), 0.9999, 213360.0, 0.0),
+    tm(203, 31.0, dm(-113.0, 45.0), 0.999_933_333, 213360.0, 0.0),
+    lcc(301, dm(34.0, 56.0), dm(36.0, 14.0), dm(34.0, 20.0), -92.0, 400000.0, 0.0),
+    lcc(302, dm(33.0, 18.0), dm(34.0, 46.0), dm(32.0, 40.0), -92.0, 400000.0, 400000.0),
+    lcc(401, 40.0, dm(41.0, 40.0), dm(39.0, 20.0), -122.0, 2000000.0, 500000.0),
+    lcc(402, dm(38.0, 20.0), dm(39.0, 50.0), dm(37.0, 40.0), -122.0, 2000000.0, 500000.0),
+    lcc(403, dm(37.0, 4.0), dm(38.0, 26.0), dm(36.0, 30.0), dm(-120.0, 30.0), 2000000.0, 500000.0),
+    lcc(404, 36.0, dm(37.0, 15.0), dm(35.0, 20.0), -119.0, 2000000.0, 500000.0),
+    lcc(405, dm(34.0, 2.0), dm(35.0, 28.0), dm(33.0, 30.0), -118.0, 2000000.0, 500000.0),
+    lcc(406, dm(32.0, 47.0), dm(33.0, 53.0), dm(32.0, 10.0), dm(-116.0, 15.0), 2000000.0, 500000.0),
+    lcc(501, dm(39.0, 43.0), dm(40.0, 47.0), dm(39.0, 20.0), dm(-105.0, 30.0), 914401.8289, 304800.6096),
+    lcc(502, dm(38.0, 27.0), dm(39.0, 45.0), dm(37.0, 50.0), dm(-105.0, 30.0), 914401.8289, 304800.6096),
+    lcc(503, dm(37.0, 14.0), dm(38.0, 26.0), dm(36.0, 40.0), dm(-105.0, 30.0), 914401.8289, 304800.6096),
+    lcc(600, dm(41.0, 12.0), dm(41.0, 52.0), dm(40.0, 50.0), dm(-72.0, 45.0), 304800.6096, 152400.3048),
+    tm(700, 38.0, dm(-75.0, 25.0), 0.999_995, 200000.0, 0.0),
+    tm(901, dm(24.0, 20.0), -81.0, 0.999_941_177, 200000.0, 0.0),
+    tm(902, dm(24.0, 20.0), -82.0, 0.999_941_177, 200000.0, 0.0),
+    lcc(903, dm(29.0, 35.0), dm(30.0, 45.0), 29.0, dm(-84.0, 30.0), 600000.0, 0.0),
+    tm(1001, 30.0, dm(-82.0, 10.0), 0.9999, 200000.0, 0.0),
+    tm(1002, 30.0, dm(-84.0, 10.0), 0.9999, 700000.0, 0.0),
+    tm(1101, dm(41.0, 40.0), dm(-112.0, 10.0), 0.999_947_368, 200000.0, 0.0),
+    tm(1102, dm(41.0, 40.0), -114.0, 0.999_947_368, 500000.0, 0.0),
+    tm(1103, dm(41.0, 40.0), dm(-115.0, 45.0), 0.999_933_333, 800000.0, 0.0),
+    tm(1201, dm(36.0, 40.0), dm(-88.0, 20.0), 0.999_975, 300000.0, 0.0),
+    tm(1202, dm(36.0, 40.0), dm(-90.0, 10.0), 0.999_941_177, 700000.0, 0.0),
+    tm(1301, dm(37.0, 30.0), dm(-85.0, 40.0), 0.999_966_667, 100000.0, 250000.0),
+    tm(1302, dm(37.0, 30.0), dm(-87.0, 5.0), 0.999_966_667, 900000.0, 250000.0),
+    lcc(1401, dm(42.0, 4.0), dm(43.0, 16.0), dm(41.0, 30.0), dm(-93.0, 30.0), 1500000.0, 1000000.0),
+    lcc(1402, dm(40.0, 37.0), dm(41.0, 47.0), 40.0, dm(-93.0, 30.0), 500000.0, 0.0),
+    lcc(1501, dm(38.0, 43.0), dm(39.0, 47.0), dm(38.0, 20.0), -98.0, 400000.0, 0.0),
+    lcc(1502, dm(37.0, 16.0), dm(38.0, 34.0), dm(36.0, 40.0), dm(-98.0, 30.0), 400000.0, 400000.0),
+    lcc(1600, dm(37.0, 5.0), dm(38.0, 40.0), dm(36.0, 20.0), dm(-85.0, 45.0), 1500000.0, 1000000.0),
+    lcc(1601, dm(37.0, 58.0), dm(38.0, 58.0), dm(37.0, 30.0), dm(-84.0, 15.0), 500000.0, 0.0),
+    lcc(1602, dm(36.0, 44.0), dm(37.0, 56.0), dm(36.0, 20.0), dm(-85.0, 45.0), 500000.0, 500000.0),
+    lcc(1701, dm(31.0, 10.0), dm(32.0, 40.0), dm(30.0, 30.0), dm(-92.0, 30.0), 1000000.0, 0.0),
+    lcc(1702, dm(29.0, 18.0), dm(30.0, 42.0), dm(28.0, 30.0), dm(-91.0, 20.0), 1000000.0, 0.0),
+    lcc(1703, dm(26.0, 10.0), dm(27.0, 50.0), dm(25.0, 30.0), dm(-91.0, 20.0), 1000000.0, 0.0),
+    tm(1801, dm(43.0, 40.0), dm(-68.0, 30.0), 0.9999, 300000.0, 0.0),
+    tm(1802, dm(42.0, 50.0), dm(-70.0, 10.0), 0.999_966_667, 900000.0, 0.0),
+    lcc(1900, dm(38.0, 18.0), dm(39.0, 27.0), dm(37.0, 40.0), -77.0, 400000.0, 0.0),
+    lcc(2001, dm(41.0, 43.0), dm(42.0, 41.0), 41.0, dm(-71.0, 30.0), 200000.0, 750000.0),
+    lcc(2002, dm(41.0, 17.0), dm(41.0, 29.0), 41.0, dm(-70.0, 30.0), 500000.0, 0.0),
+    lcc(2111, dm(45.0, 29.0), dm(47.0, 5.0), dm(44.0, 47.0), -87.0, 8000000.0, 0.0),
+    lcc(2112, dm(44.0, 11.0), dm(45.0, 42.0), dm(43.0, 19.0), dm(-84.0, 22.0), 6000000.0, 0.0),
+    lcc(2113, dm(42.0, 6.0), dm(43.0, 40.0), dm(41.0, 30.0), dm(-84.0, 22.0), 4000000.0, 0.0),
+    lcc(2201, dm(47.0, 2.0), dm(48.0, 38.0), dm(46.0, 30.0), dm(-93.0, 6.0), 800000.0, 100000.0),
+    lcc(2202, dm(45.0, 37.0), dm(47.0, 3.0), 45.0, dm(-94.0, 15.0), 800000.0, 100000.0),
+    lcc(2203, dm(43.0, 47.0), dm(45.0, 13.0), 43.0, -94.0, 800000.0, 100000.0),
+    tm(2301, dm(29.0, 30.0), dm(-88.0, 50.0), 0.999_95, 300000.0, 0.0),
+    tm(2302, dm(29.0, 30.0), dm(-90.0, 20.0), 0.999_95, 700000.0, 0.0),
+    tm(2401, dm(35.0, 50.0), dm(-90.0, 30.0), 0.999_933_333, 250000.0, 0.0),
+    tm(2402, dm(35.0, 50.0), dm(-92.0, 30.0), 0.999_933_333, 500000.0, 0.0),
+    tm(2403, dm(36.0, 10.0), dm(-94.0, 30.0), 0.999_941_177, 850000.0, 0.0),
+    lcc(2500, 45.0, 49.0, dm(44.0, 15.0), dm(-109.0, 30.0), 600000.0, 0.0),
+    lcc(2600, 40.0, 43.0, dm(39.0, 50.0), -100.0, 500000.0, 0.0),
+    tm(2701, dm(34.0, 45.0), dm(-115.0, 35.0), 0.9999, 200000.0, 8000000.0),
+    tm(2702, dm(34.0, 45.0), dm(-116.0, 40.0), 0.9999, 500000.0, 6000000.0),
+    tm(2703, dm(34.0, 45.0), dm(-118.0, 35.0), 0.9999, 800000.0, 4000000.0),
+    tm(2800, dm(42.0, 30.0), dm(-71.0, 40.0), 0.999_966_667, 300000.0, 0.0),
+    tm(2900, dm(38.0, 50.0), dm(-74.0, 30.0), 0.9999, 150000.0, 0.0),
+    tm(3001, 31.0, dm(-104.0, 20.0), 0.999_909_091, 165000.0, 0.0),
+    tm(3002, 31.0, dm(-106.0, 15.0), 0.9999, 500000.0, 0.0),
+    tm(3003, 31.0, dm(-107.0, 50.0), 0.999_916_667, 830000.0, 0.0),
+    tm(3101, dm(38.0, 50.0), dm(-74.0, 30.0), 0.9999, 150000.0, 0.0),
+    tm(3102, 40.0, dm(-76.0, 35.0), 0.999_937_5, 250000.0, 0.0),
+    tm(3103, 40.0, dm(-78.0, 35.0), 0.999_937_5, 350000.0, 0.0),
+    lcc(3104, dm(40.0, 40.0), dm(41.0, 2.0), dm(40.0, 10.0), -74.0, 300000.0, 0.0),
+    lcc(3200, dm(34.0, 20.0), dm(36.0, 10.0), dm(33.0, 45.0), -79.0, 609601.22, 0.0),
+    lcc(3301, dm(47.0, 26.0), dm(48.0, 44.0), 47.0, dm(-100.0, 30.0), 600000.0, 0.0),
+    lcc(3302, dm(46.0, 11.0), dm(47.0, 29.0), dm(45.0, 40.0), dm(-100.0, 30.0), 600000.0, 0.0),
+    lcc(3401, dm(40.0, 26.0), dm(41.0, 42.0), dm(39.0, 40.0), dm(-82.0, 30.0), 600000.0, 0.0),
+    lcc(3402, dm(38.0, 44.0), dm(40.0, 2.0), 38.0, dm(-82.0, 30.0), 600000.0, 0.0),
+    lcc(3501, dm(35.0, 34.0), dm(36.0, 46.0), 35.0, -98.0, 600000.0, 0.0),
+    lcc(3502, dm(33.0, 56.0), dm(35.0, 14.0), dm(33.0, 20.0), -98.0, 600000.0, 0.0),
+    lcc(3601, dm(44.0, 20.0), 46.0, dm(43.0, 40.0), dm(-120.0, 30.0), 2500000.0, 0.0),
+    lcc(3602, dm(42.0, 20.0), 44.0, dm(41.0, 40.0), dm(-120.0, 30.0), 1500000.0, 0.0),
+    lcc(3701, dm(40.0, 53.0), dm(41.0, 57.0), dm(40.0, 10.0), dm(-77.0, 45.0), 600000.0, 0.0),
+    lcc(3702, dm(39.0, 56.0), dm(40.0, 58.0), dm(39.0, 20.0), dm(-77.0, 45.0), 600000.0, 0.0),
+    tm(3800, dm(41.0, 5.0), dm(-71.0, 30.0), 0.999_993_75, 100000.0, 0.0),
+    lcc(3900, dm(32.0, 30.0), dm(34.0, 50.0), dm(31.0, 50.0), -81.0, 609600.0, 0.0),
+    lcc(4001, dm(44.0, 25.0), dm(45.0, 41.0), dm(43.0, 50.0), -100.0, 600000.0, 0.0),
+    lcc(4002, dm(42.0, 50.0), dm(44.0, 24.0), dm(42.0, 20.0), dm(-100.0, 20.0), 600000.0, 0.0),
+    lcc(4100, dm(35.0, 15.0), dm(36.0, 25.0), dm(34.0, 20.0), -86.0, 600000.0, 0.0),
+    lcc(4201, dm(34.0, 39.0), dm(36.0, 11.0), 34.0, dm(-101.0, 30.0), 200000.0, 1000000.0),
+    lcc(4202, dm(32.0, 8.0), dm(33.0, 58.0), dm(31.0, 40.0), dm(-98.0, 30.0), 600000.0, 2000000.0),
+    lcc(4203, dm(30.0, 7.0), dm(31.0, 53.0), dm(29.0, 40.0), dm(-100.0, 20.0), 700000.0, 3000000.0),
+    lcc(4204, dm(28.0, 23.0), dm(30.0, 17.0), dm(27.0, 50.0), -99.0, 600000.0, 4000000.0),
+    lcc(4205, dm(26.0, 10.0), dm(27.0, 50.0), dm(25.0, 40.0), dm(-98.0, 30.0), 300000.0, 5000000.0),
+    lcc(4301, dm(40.0, 43.0), dm(41.0, 47.0), dm(40.0, 20.0), dm(-111.0, 30.0), 500000.0, 1000000.0),
+    lcc(4302, dm(39.0, 1.0), dm(40.0, 39.0), dm(38.0, 20.0), dm(-111.0, 30.0), 500000.0, 2000000.0),
+    lcc(4303, dm(37.0, 13.0), dm(38.0, 21.0), dm(36.0, 40.0), dm(-111.0, 30.0), 500000.0, 3000000.0),
+    tm(4400, dm(42.0, 30.0), dm(-72.0, 30.0), 0.999_964_286, 500000.0, 0.0),
+    lcc(4501, dm(38.0, 2.0), dm(39.0, 12.0), dm(37.0, 40.0), dm(-78.0, 30.0), 3500000.0, 2000000.0),
+    lcc(4502, dm(36.0, 46.0), dm(37.0, 58.0), dm(36.0, 20.0), dm(-78.0, 30.0), 3500000.0, 1000000.0),
+    lcc(4601, dm(47.0, 30.0), dm(48.0, 44.0), 47.0, dm(-120.0, 50.0), 500000.0, 0.0),
+    lcc(4602, dm(45.0, 50.0), dm(47.0, 20.0), dm(45.0, 20.0), dm(-120.0, 30.0), 500000.0, 0.0),
+    lcc(4701, 39.0, dm(40.0, 15.0), dm(38.0, 30.0), dm(-79.0, 30.0), 600000.0, 0.0),
+    lcc(4702, dm(37.0, 29.0), dm(38.0, 53.0), 37.0, -81.0, 600000.0, 0.0),
+    lcc(4801, dm(45.0, 34.0), dm(46.0, 46.0), dm(45.0, 10.0), -90.0, 600000.0, 0.0),
+    lcc(4802, dm(44.0, 15.0), dm(45.0, 30.0), dm(43.0, 50.0), -90.0, 600000.0, 0.0),
+    lcc(4803, dm(42.0, 44.0), dm(44.0, 4.0), 42.0, -90.0, 600000.0, 0.0),
+    tm(4901, dm(40.0, 30.0), dm(-105.0, 10.0), 0.999_937_5, 200000.0, 0.0),
+    tm(4902, dm(40.0, 30.0), dm(-107.0, 20.0), 0.999_937_5, 400000.0, 100000.0),
+    tm(4903, dm(40.0, 30.0), dm(-108.0, 45.0), 0.999_937_5, 600000.0, 0.0),
+    tm(4904, dm(40.0, 30.0), dm(-110.0, 5.0), 0.999_937_5, 800000.0, 100000.0),
+    tm(5002, 54.0, -142.0, 0.9999, 500000.0, 0.0),
+    tm(5003, 54.0, -146.0, 0.9999, 500000.0, 0.0),
+    tm(5004, 54.0, -150.0, 0.9999, 500000.0, 0.0),
+    tm(5005, 54.0, -154.0, 0.9999, 500000.0, 0.0),
+    tm(5006, 54.0, -158.0, 0.9999, 500000.0, 0.0),
+    tm(5007, 54.0, -162.0, 0.9999, 500000.0, 0.0),
+    tm(5008, 54.0, -166.0, 0.9999, 500000.0, 0.0),
+    tm(5009, 54.0, -170.0, 0.9999, 500000.0, 0.0),
+    lcc(5010, dm(51.0, 50.0), dm(53.0, 50.0), 51.0, -176.0, 1000000.0, 0.0),
+    tm(5101, dm(18.0, 50.0), dm(-155.0, 30.0), 0.999_966_667, 500000.0, 0.0),
+    tm(5102, dm(20.0, 20.0), dm(-156.0, 40.0), 0.999_966_667, 500000.0, 0.0),
+    tm(5103, dm(21.0, 10.0), -158.0, 0.999_99, 500000.0, 0.0),
+    tm(5104, dm(21.0, 50.0), dm(-159.0, 30.0), 0.999_99, 500000.0, 0.0),
+    tm(5105, dm(21.0, 40.0), dm(-160.0, 10.0), 1.0, 500000.0, 0.0),
+    lcc(5200, dm(18.0, 2.0), dm(18.0, 26.0), dm(17.0, 50.0), dm(-66.0, 26.0), 200000.0, 200000.0)
+];
+
+/// Looks up a NAD83 State Plane Coordinate System zone
+/// 
+/// # Arguments
+/// 
+/// * `code` - FIPS zone code, e.g. 405 for California V
+/// 
+/// # Return Value
+/// 
+/// * Option<ProjectedCrs> - the zone's projection on GRS80 in meters, or None for an unknown code
+pub fn zone(code: u16) -> Option<ProjectedCrs> {
+    let definition = ZONES.iter().find(|definition| definition.code == code)?;
+    let grs80 = geo_ellipsoid::GeoEllipsoid::grs80();
+    let lat0 = definition.lat0.to_radians();
+    let lon0 = definition.lon0.to_radians();
+    Some(match definition.method {
+        ZoneMethod::TransverseMercator(scale_factor) => ProjectedCrs::TransverseMercator(
+            TransverseMercator::new(lat0, lon0, scale_factor, definition.false_easting, definition.false_northing, &grs80)),
+        ZoneMethod::LambertConformalConic(sp1, sp2) => ProjectedCrs::LambertConformalConic(
+            LambertConformalConic::new(lat0, lon0, sp1.to_radians(), sp2.to_radians(),
+                                       definition.false_easting, definition.false_northing, &grs80))
+    })
+}
+
+/// FIPS codes of all supported zones, in ascending order
+pub fn zone_codes() -> Vec<u16> {
+    ZONES.iter().map(|definition| definition.code).collect()
+}
+
+/// Converts 3-d NAD83 LLA coordinates to State Plane coordinates
+/// 
+/// # Arguments
+/// 
+/// * `lla_vec` - Vector3 reference to the NAD83 LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+/// * `code` - FIPS zone code
+/// 
+/// # Return Value
+/// 
+/// * Option<nalgebra::Vector3<f64>> - easting, northing, altitude (meters), or None for an unknown code
+pub fn lla2spcs(lla_vec: &Vector3<f64>, code: u16) -> Option<Vector3<f64>> {
+    zone(code).map(|projection| projection.forward(lla_vec))
+}
+
+/// Converts State Plane coordinates to 3-d NAD83 LLA coordinates
+/// 
+/// # Arguments
+/// 
+/// * `spcs_vec` - Vector3 reference to the State Plane vector (easting, northing, altitude) (meters)
+/// * `code` - FIPS zone code
+/// 
+/// # Return Value
+/// 
+/// * Option<nalgebra::Vector3<f64>> - lat, long, alt (radians, radians, meters), or None for an unknown code
+pub fn spcs2lla(spcs_vec: &Vector3<f64>, code: u16) -> Option<Vector3<f64>> {
+    zone(code).map(|projection| projection.inverse(spcs_vec))
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_zone() {
+        assert!(zone(405).is_some());
+        assert!(zone(5001).is_none());
+        assert!(zone(0).is_none());
+        let codes = zone_codes();
+        assert_eq!(codes.len(), ZONES.len());
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+        match zone(405) {
+            Some(ProjectedCrs::LambertConformalConic(_)) => {},
+            _ => panic!("California V is a Lambert conformal conic zone")
+        }
+        match zone(202) {
+            Some(ProjectedCrs::TransverseMercator(projection)) => assert_eq!(projection.get_scale_factor(), 0.9999),
+            _ => panic!("Arizona Central is a Transverse Mercator zone")
+        }
+    }
+    #[test]
+    fn test_lla2spcs() {
+        //Zone origins map to the false offsets
+        let origin = lla2spcs(&Vector3::new(dm(33.0, 30.0).to_radians(), -118.0f64.to_radians(), 0.0), 405).unwrap();
+        assert_approx_eq!(origin.x, 2000000.0, 1e-6);
+        assert_approx_eq!(origin.y, 500000.0, 1e-6);
+        let origin = lla2spcs(&Vector3::new(31.0f64.to_radians(), dm(-111.0, 55.0).to_radians(), 0.0), 202).unwrap();
+        assert_approx_eq!(origin.x, 213360.0, 1e-6);
+        assert_approx_eq!(origin.y, 0.0, 1e-6);
+        assert_eq!(lla2spcs(&Vector3::new(0.0, 0.0, 0.0), 9999), None);
+        //The defining scales of the NOS NGS 5 zone definitions: California V is true to scale on its standard parallels
+        //34 02 N and 35 28 N, Arizona Central has the scale 0.9999 on its central meridian 111 55 W
+        let grs80 = geo_ellipsoid::GeoEllipsoid::grs80();
+        let dlon = 1e-6f64;
+        let scale = |lat: f64, lon: f64, code: u16| {
+            let west = lla2spcs(&Vector3::new(lat, lon - dlon, 0.0), code).unwrap();
+            let east = lla2spcs(&Vector3::new(lat, lon + dlon, 0.0), code).unwrap();
+            (east - west).norm() / (2.0 * dlon * grs80.radius_prime_vertical(lat) * lat.cos())
+        };
+        assert_approx_eq!(scale(dm(34.0, 2.0).to_radians(), -118.0f64.to_radians(), 405), 1.0, 1e-9);
+        assert_approx_eq!(scale(dm(35.0, 28.0).to_radians(), -118.0f64.to_radians(), 405), 1.0, 1e-9);
+        assert!(scale(dm(34.0, 45.0).to_radians(), -118.0f64.to_radians(), 405) < 1.0 - 5e-5);
+        for lat_deg in [31.0f64, 33.4484, 37.0].iter() {
+            assert_approx_eq!(scale(lat_deg.to_radians(), dm(-111.0, 55.0).to_radians(), 202), 0.9999, 1e-9);
+        }
+    }
+    #[test]
+    fn test_spcs2lla() {
+        for code in zone_codes() {
+            let definition = ZONES.iter().find(|definition| definition.code == code).unwrap();
+            let lla_vec = Vector3::new((definition.lat0 + 1.5).to_radians(), (definition.lon0 + 0.7).to_radians(), 10.0);
+            let back = spcs2lla(&lla2spcs(&lla_vec, code).unwrap(), code).unwrap();
+            assert_approx_eq!(back.x, lla_vec.x, 1e-11);
+            assert_approx_eq!(back.y, lla_vec.y, 1e-11);
+            assert_eq!(back.z, 10.0);
+        }
+        assert_eq!(spcs2lla(&Vector3::new(0.0, 0.0, 0.0), 5001), None);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+        for projection in [zone(405).unwrap(), zone(202).unwrap(), ProjectedCrs::LongLat].iter() {
+            let back: ProjectedCrs = serde_json::from_str(&serde_json::to_string(projection).unwrap()).unwrap();
+            assert_eq!(&back, projection);
+        }
+    }
+}
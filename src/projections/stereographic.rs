@@ -0,0 +1,418 @@
+use na::Vector3;
+use std::f64;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use structs::utm::Hemisphere;
+use projections::{Projection, conformal_t, conformal_t_inverse, isometric_latitude, isometric_latitude_inverse};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Aspect of a stereographic projection
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Aspect {
+    /// Centered on a pole, with the polar stereographic radius factor
+    Polar(Hemisphere, f64),
+    /// Centered on an arbitrary point through the conformal sphere (EPSG method 9809)
+    Oblique {
+        conformal_lat0: f64,
+        exponent: f64,
+        c: f64,
+        radius: f64
+    }
+}
+
+/// Constructor arguments of a stereographic projection, its serialized form
+/// 
+/// A polar projection with a latitude of true scale is kept as its hemisphere and scale factor at the pole.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Definition {
+    /// Arguments of Stereographic::new_polar
+    Polar {
+        hemisphere: Hemisphere,
+        lon0: f64,
+        scale_factor: f64,
+        false_easting: f64,
+        false_northing: f64,
+        ellipsoid: geo_ellipsoid::GeoEllipsoid
+    },
+    /// Arguments of Stereographic::new_oblique
+    Oblique {
+        lat0: f64,
+        lon0: f64,
+        scale_factor: f64,
+        false_easting: f64,
+        false_northing: f64,
+        ellipsoid: geo_ellipsoid::GeoEllipsoid
+    }
+}
+
+/// Ellipsoidal stereographic projection in the polar or oblique aspect
+/// 
+/// The point antipodal to the center has no finite image, Projection::project rejects it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stereographic {
+    lon0: f64,
+    scale_factor: f64,
+    false_easting: f64,
+    false_northing: f64,
+    ecc: f64,
+    aspect: Aspect,
+    definition: Definition
+}
+
+impl Stereographic {
+    /// Creates a polar stereographic projection with a scale factor at the pole (EPSG variant A)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `hemisphere` - pole at the center of the projection
+    /// * `lon0` - longitude pointing from the pole towards the false origin's south (north pole) or north (south pole) (radians)
+    /// * `scale_factor` - scale factor at the pole
+    /// * `false_easting` - easting of the pole (meters)
+    /// * `false_northing` - northing of the pole (meters)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    pub fn new_polar(hemisphere: Hemisphere, lon0: f64, scale_factor: f64, false_easting: f64, false_northing: f64,
+                     ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> Stereographic {
+        let ecc = ellipsoid.get_first_ecc();
+        let radius_factor = 2.0 * ellipsoid.get_semi_major_axis() * scale_factor
+            / ((1.0 + ecc).powf(1.0 + ecc) * (1.0 - ecc).powf(1.0 - ecc)).sqrt();
+        Stereographic {
+            lon0,
+            scale_factor,
+            false_easting,
+            false_northing,
+            ecc,
+            aspect: Aspect::Polar(hemisphere, radius_factor),
+            definition: Definition::Polar { hemisphere, lon0, scale_factor, false_easting, false_northing, ellipsoid: *ellipsoid }
+        }
+    }
+
+    /// Creates a polar stereographic projection with true scale along a parallel (EPSG variant B)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat_ts` - latitude of true scale (radians), its sign selects the pole
+    /// * `lon0` - longitude of origin (radians)
+    /// * `false_easting` - easting of the pole (meters)
+    /// * `false_northing` - northing of the pole (meters)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// 
+    /// # Formula
+    /// 
+    /// * k0 = m_ts * sqrt((1 + e)^(1 + e) * (1 - e)^(1 - e)) / (2 * t_ts)
+    pub fn new_polar_true_scale(lat_ts: f64, lon0: f64, false_easting: f64, false_northing: f64,
+                                ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> Stereographic {
+        let ecc = ellipsoid.get_first_ecc();
+        let hemisphere = if lat_ts < 0.0 { Hemisphere::South } else { Hemisphere::North };
+        let lat_ts = lat_ts.abs();
+        let scale_factor = if (f64::consts::FRAC_PI_2 - lat_ts).abs() < 1e-12 {
+            1.0
+        } else {
+            let m = lat_ts.cos() / (1.0 - (ecc * lat_ts.sin()).powi(2)).sqrt();
+            m * ((1.0 + ecc).powf(1.0 + ecc) * (1.0 - ecc).powf(1.0 - ecc)).sqrt() / (2.0 * conformal_t(lat_ts, ecc))
+        };
+        Stereographic::new_polar(hemisphere, lon0, scale_factor, false_easting, false_northing, ellipsoid)
+    }
+
+    /// Creates an oblique stereographic projection through the conformal sphere (EPSG method 9809)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat0` - latitude of natural origin (radians)
+    /// * `lon0` - longitude of natural origin (radians)
+    /// * `scale_factor` - scale factor at the natural origin
+    /// * `false_easting` - easting of the natural origin (meters)
+    /// * `false_northing` - northing of the natural origin (meters)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    /// 
+    /// # Formula
+    /// 
+    /// * R = sqrt(M0 * N0), n = sqrt(1 + e^2 * cos(lat0)^4 / (1 - e^2))
+    /// * w1 = (S1 * S2^e)^n with S1 = (1 + sin(lat0)) / (1 - sin(lat0)), S2 = (1 - e * sin(lat0)) / (1 + e * sin(lat0))
+    /// * c = (n + sin(lat0)) * (1 - sin(chi)) / ((n - sin(lat0)) * (1 + sin(chi))) with sin(chi) = (w1 - 1) / (w1 + 1)
+    /// * chi0 = asin((c * w1 - 1) / (c * w1 + 1))
+    pub fn new_oblique(lat0: f64, lon0: f64, scale_factor: f64, false_easting: f64, false_northing: f64,
+                       ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> Stereographic {
+        let ecc = ellipsoid.get_first_ecc();
+        let e2 = ecc.powi(2);
+        let a = ellipsoid.get_semi_major_axis();
+        let sin_lat0 = lat0.sin();
+        let w = 1.0 - e2 * sin_lat0.powi(2);
+        let radius = (a * (1.0 - e2) / w.powf(1.5) * a / w.sqrt()).sqrt();
+        let exponent = (1.0 + e2 * lat0.cos().powi(4) / (1.0 - e2)).sqrt();
+        let w1 = ((1.0 + sin_lat0) / (1.0 - sin_lat0) * ((1.0 - ecc * sin_lat0) / (1.0 + ecc * sin_lat0)).powf(ecc)).powf(exponent);
+        let sin_chi = (w1 - 1.0) / (w1 + 1.0);
+        let c = (exponent + sin_lat0) * (1.0 - sin_chi) / ((exponent - sin_lat0) * (1.0 + sin_chi));
+        Stereographic {
+            lon0,
+            scale_factor,
+            false_easting,
+            false_northing,
+            ecc,
+            aspect: Aspect::Oblique {
+                conformal_lat0: ((c * w1 - 1.0) / (c * w1 + 1.0)).asin(),
+                exponent,
+                c,
+                radius
+            },
+            definition: Definition::Oblique { lat0, lon0, scale_factor, false_easting, false_northing, ellipsoid: *ellipsoid }
+        }
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    pub fn get_false_easting(&self) -> f64 {
+        self.false_easting
+    }
+
+    pub fn get_false_northing(&self) -> f64 {
+        self.false_northing
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - easting, northing, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * polar: rho = 2 * a * k0 * t / sqrt((1 + e)^(1 + e) * (1 - e)^(1 - e)), easting = FE + rho * sin(dlon), northing = FN -/+ rho * cos(dlon)
+    /// * oblique: chi = conformal latitude, L = n * dlon, B = 1 + sin(chi) * sin(chi0) + cos(chi) * cos(chi0) * cos(L)
+    /// * oblique: easting = FE + 2 * R * k0 * cos(chi) * sin(L) / B, northing = FN + 2 * R * k0 * (sin(chi) * cos(chi0) - cos(chi) * sin(chi0) * cos(L)) / B
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let dlon = geo::wrap_longitude(lla_vec.y - self.lon0);
+        let (x, y) = match self.aspect {
+            Aspect::Polar(Hemisphere::North, radius_factor) => {
+                let rho = radius_factor * conformal_t(lla_vec.x, self.ecc);
+                (rho * dlon.sin(), -rho * dlon.cos())
+            },
+            Aspect::Polar(Hemisphere::South, radius_factor) => {
+                let rho = radius_factor * conformal_t(-lla_vec.x, self.ecc);
+                (rho * dlon.sin(), rho * dlon.cos())
+            },
+            Aspect::Oblique { conformal_lat0, exponent, radius, .. } => {
+                let chi = self.conformal_latitude(lla_vec.x);
+                let lambda = exponent * dlon;
+                let b = 1.0 + chi.sin() * conformal_lat0.sin() + chi.cos() * conformal_lat0.cos() * lambda.cos();
+                let scale = 2.0 * radius * self.scale_factor / b;
+                (scale * chi.cos() * lambda.sin(),
+                 scale * (chi.sin() * conformal_lat0.cos() - chi.cos() * conformal_lat0.sin() * lambda.cos()))
+            }
+        };
+        Vector3::new(self.false_easting + x, self.false_northing + y, lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (easting, northing, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * polar: t = rho * sqrt((1 + e)^(1 + e) * (1 - e)^(1 - e)) / (2 * a * k0), lat iterated from t
+    /// * oblique: spherical stereographic inverse on the conformal sphere of radius R * k0, then
+    ///   lon = lon0 + L / n and lat iterated from the isometric latitude ln((1 + sin(chi)) / (c * (1 - sin(chi)))) / (2 * n)
+    /// 
+    /// The longitude of the center itself is lon0.
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let x = proj_vec.x - self.false_easting;
+        let y = proj_vec.y - self.false_northing;
+        let rho = x.hypot(y);
+        let (lat, dlon) = match self.aspect {
+            Aspect::Polar(hemisphere, radius_factor) => {
+                let lat = conformal_t_inverse(rho / radius_factor, self.ecc);
+                match hemisphere {
+                    Hemisphere::North => (lat, x.atan2(-y)),
+                    Hemisphere::South => (-lat, x.atan2(y))
+                }
+            },
+            Aspect::Oblique { conformal_lat0, exponent, c, radius } => {
+                let angle = 2.0 * (rho / (2.0 * radius * self.scale_factor)).atan();
+                let (sin_chi0, cos_chi0) = conformal_lat0.sin_cos();
+                let chi = if rho == 0.0 {
+                    conformal_lat0
+                } else {
+                    (angle.cos() * sin_chi0 + y * angle.sin() * cos_chi0 / rho).clamp(-1.0, 1.0).asin()
+                };
+                let lambda = (x * angle.sin()).atan2(rho * cos_chi0 * angle.cos() - y * sin_chi0 * angle.sin());
+                let psi = ((1.0 + chi.sin()) / (c * (1.0 - chi.sin()))).ln() / (2.0 * exponent);
+                (isometric_latitude_inverse(psi, self.ecc), lambda / exponent)
+            }
+        };
+        let lon = if rho == 0.0 { self.lon0 } else { geo::wrap_longitude(self.lon0 + dlon) };
+        Vector3::new(lat, lon, proj_vec.z)
+    }
+
+    /// Latitude on the conformal sphere of the oblique aspect
+    fn conformal_latitude(&self, lat: f64) -> f64 {
+        match self.aspect {
+            Aspect::Oblique { exponent, c, .. } => {
+                let w = c * (2.0 * exponent * isometric_latitude(lat, self.ecc)).exp();
+                if w.is_infinite() {
+                    f64::consts::FRAC_PI_2
+                } else {
+                    ((w - 1.0) / (w + 1.0)).asin()
+                }
+            },
+            Aspect::Polar(..) => lat
+        }
+    }
+}
+
+impl Projection for Stereographic {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        let antipodal = match self.aspect {
+            Aspect::Polar(Hemisphere::North, _) => lla_vec.x <= 1e-12 - f64::consts::FRAC_PI_2,
+            Aspect::Polar(Hemisphere::South, _) => lla_vec.x >= f64::consts::FRAC_PI_2 - 1e-12,
+            Aspect::Oblique { conformal_lat0, exponent, .. } => {
+                let chi = self.conformal_latitude(lla_vec.x);
+                let lambda = exponent * geo::wrap_longitude(lla_vec.y - self.lon0);
+                1.0 + chi.sin() * conformal_lat0.sin() + chi.cos() * conformal_lat0.cos() * lambda.cos() < 1e-12
+            }
+        };
+        if antipodal {
+            Err(GeoError::OutsideProjectionDomain)
+        } else {
+            Ok(self.forward(lla_vec))
+        }
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+/// Serializes the constructor arguments only
+#[cfg(feature = "serde")]
+impl Serialize for Stereographic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.definition.serialize(serializer)
+    }
+}
+
+/// Rebuilds the derived values through the constructor, the ellipsoid is validated by its own deserializer
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Stereographic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Stereographic, D::Error> {
+        Ok(match Definition::deserialize(deserializer)? {
+            Definition::Polar { hemisphere, lon0, scale_factor, false_easting, false_northing, ellipsoid } =>
+                Stereographic::new_polar(hemisphere, lon0, scale_factor, false_easting, false_northing, &ellipsoid),
+            Definition::Oblique { lat0, lon0, scale_factor, false_easting, false_northing, ellipsoid } =>
+                Stereographic::new_oblique(lat0, lon0, scale_factor, false_easting, false_northing, &ellipsoid)
+        })
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward_polar() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        //EPSG guidance note 7-2 example, WGS 84 / UPS North (variant A)
+        let ups = Stereographic::new_polar(Hemisphere::North, 0.0, 0.994, 2000000.0, 2000000.0, &ellipsoid);
+        let proj_vec = ups.forward(&Vector3::new(73.0f64.to_radians(), 44.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 3320416.75, 0.005);
+        assert_approx_eq!(proj_vec.y, 632668.43, 0.005);
+        //EPSG guidance note 7-2 example, WGS 84 / Australian Antarctic Polar Stereographic (variant B)
+        let aat = Stereographic::new_polar_true_scale(-71.0f64.to_radians(), 70.0f64.to_radians(), 6000000.0, 6000000.0, &ellipsoid);
+        let proj_vec = aat.forward(&Vector3::new(-75.0f64.to_radians(), 120.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 7255380.79, 0.005);
+        assert_approx_eq!(proj_vec.y, 7053389.56, 0.005);
+        //WGS 84 / NSIDC Sea Ice Polar Stereographic North (EPSG:3413), true scale at 70 N
+        let nsidc = Stereographic::new_polar_true_scale(70.0f64.to_radians(), -45.0f64.to_radians(), 0.0, 0.0, &ellipsoid);
+        let pole = nsidc.forward(&Vector3::new(f64::consts::FRAC_PI_2, 1.0, 0.0));
+        assert_approx_eq!(pole.x, 0.0, 1e-9);
+        assert_approx_eq!(pole.y, 0.0, 1e-9);
+        //Points on the central meridian lie south of the pole along -y
+        //True scale makes the 70 N parallel a circle of radius N * cos(70 N) along -y on the central meridian
+        let lat_ts = 70.0f64.to_radians();
+        let proj_vec = nsidc.forward(&Vector3::new(lat_ts, -45.0f64.to_radians(), 0.0));
+        let e2 = ellipsoid.get_first_ecc_squared();
+        assert_approx_eq!(proj_vec.x, 0.0, 1e-6);
+        assert_approx_eq!(proj_vec.y, -ellipsoid.get_semi_major_axis() * lat_ts.cos() / (1.0 - e2 * lat_ts.sin().powi(2)).sqrt(), 1e-6);
+        assert_eq!(nsidc.project(&Vector3::new(-f64::consts::FRAC_PI_2, 0.0, 0.0)), Err(GeoError::OutsideProjectionDomain));
+    }
+    #[test]
+    fn test_forward_oblique() {
+        //EPSG guidance note 7-2 example, Amersfoort / RD New on the Bessel 1841 ellipsoid
+        let bessel = geo_ellipsoid::GeoEllipsoid::new(6377397.155, 299.15281);
+        let lat0 = 52.0f64 + 9.0 / 60.0 + 22.178 / 3600.0;
+        let lon0 = 5.0f64 + 23.0 / 60.0 + 15.500 / 3600.0;
+        let rd = Stereographic::new_oblique(lat0.to_radians(), lon0.to_radians(), 0.9999079, 155000.0, 463000.0, &bessel);
+        let proj_vec = rd.forward(&Vector3::new(53.0f64.to_radians(), 6.0f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 196105.283, 0.001);
+        assert_approx_eq!(proj_vec.y, 557057.739, 0.001);
+        let back = rd.inverse(&proj_vec);
+        assert_approx_eq!(back.x, 53.0f64.to_radians(), 1e-12);
+        assert_approx_eq!(back.y, 6.0f64.to_radians(), 1e-12);
+        let center = rd.forward(&Vector3::new(lat0.to_radians(), lon0.to_radians(), 0.0));
+        assert_approx_eq!(center.x, 155000.0, 1e-6);
+        assert_approx_eq!(center.y, 463000.0, 1e-6);
+        //The unrepresentable point is antipodal to the center on the conformal sphere
+        if let Aspect::Oblique { conformal_lat0, exponent, c, .. } = rd.aspect {
+            let sin_chi = -conformal_lat0.sin();
+            let lat = isometric_latitude_inverse(((1.0 + sin_chi) / (c * (1.0 - sin_chi))).ln() / (2.0 * exponent), rd.ecc);
+            let antipode = Vector3::new(lat, lon0.to_radians() - f64::consts::PI / exponent, 0.0);
+            assert_eq!(rd.project(&antipode), Err(GeoError::OutsideProjectionDomain));
+            assert!(rd.project(&Vector3::new(lat + 0.01, antipode.y, 0.0)).is_ok());
+        }
+    }
+    #[test]
+    fn test_inverse() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let projections = [Stereographic::new_polar(Hemisphere::North, 0.3, 0.994, 2000000.0, 2000000.0, &ellipsoid),
+                           Stereographic::new_polar_true_scale(-71.0f64.to_radians(), 70.0f64.to_radians(), 0.0, 0.0, &ellipsoid),
+                           Stereographic::new_oblique(0.6, -1.2, 0.9999, 100000.0, 200000.0, &ellipsoid),
+                           Stereographic::new_oblique(-0.2, 2.5, 1.0, 0.0, 0.0, &ellipsoid)];
+        //Round trips to sub-millimeter over the hemisphere facing each center
+        for stereo in projections.iter() {
+            for lat_deg in [-85.0f64, -45.0, -5.0, 0.0, 20.0, 60.0, 89.0].iter() {
+                for dlon_deg in [-80.0f64, -10.0, 0.0, 35.0, 80.0].iter() {
+                    let lla_vec = Vector3::new(lat_deg.to_radians(), stereo.get_lon0() + dlon_deg.to_radians(), 0.0);
+                    let proj_vec = stereo.forward(&lla_vec);
+                    if (proj_vec.x - stereo.get_false_easting()).hypot(proj_vec.y - stereo.get_false_northing()) > 2.5e7 {
+                        continue;
+                    }
+                    let back_proj = stereo.forward(&stereo.inverse(&proj_vec));
+                    assert_approx_eq!(back_proj.x, proj_vec.x, 1e-4);
+                    assert_approx_eq!(back_proj.y, proj_vec.y, 1e-4);
+                }
+            }
+        }
+        let pole = projections[0].inverse(&Vector3::new(2000000.0, 2000000.0, 0.0));
+        assert_eq!(pole, Vector3::new(f64::consts::FRAC_PI_2, 0.3, 0.0));
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+        let wgs84 = geo_ellipsoid::GeoEllipsoid::wgs84();
+        for stereographic in [Stereographic::new_polar_true_scale(-1.2, 0.5, 6000.0, 7000.0, &wgs84),
+                              Stereographic::new_oblique(0.9, 0.1, 0.9999, 155000.0, 463000.0, &wgs84)].iter() {
+            let back: Stereographic = serde_json::from_str(&serde_json::to_string(stereographic).unwrap()).unwrap();
+            assert_eq!(&back, stereographic);
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use na::Vector3;
+use geo;
+use geo::GeoError;
+use structs::geo_ellipsoid;
+use projections::Projection;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Constructor arguments of a transverse Mercator projection, its serialized form
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Definition {
+    lat0: f64,
+    lon0: f64,
+    scale_factor: f64,
+    false_easting: f64,
+    false_northing: f64,
+    ellipsoid: geo_ellipsoid::GeoEllipsoid
+}
+
+/// Ellipsoidal transverse Mercator projection using the Kruger series to order n^6
+/// 
+/// The series are accurate to well below a millimeter within several degrees of the
+/// central meridian and remain usable out to roughly 30 degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransverseMercator {
+    lat0: f64,
+    lon0: f64,
+    scale_factor: f64,
+    false_easting: f64,
+    false_northing: f64,
+    ecc: f64,
+    semi_major_axis: f64,
+    rectifying_radius: f64,
+    alpha: [f64; 6],
+    beta: [f64; 6],
+    origin_northing: f64,
+    definition: Definition
+}
+
+impl TransverseMercator {
+    /// Creates a transverse Mercator projection
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lat0` - latitude of natural origin (radians)
+    /// * `lon0` - longitude of the central meridian (radians)
+    /// * `scale_factor` - scale factor on the central meridian
+    /// * `false_easting` - easting of the natural origin (meters)
+    /// * `false_northing` - northing of the natural origin (meters)
+    /// * `ellipsoid` - GeoEllipsoid reference to the ellipsoid
+    pub fn new(lat0: f64, lon0: f64, scale_factor: f64, false_easting: f64, false_northing: f64,
+               ellipsoid: &geo_ellipsoid::GeoEllipsoid) -> TransverseMercator {
+        let n = ellipsoid.get_third_flattening();
+        let (n2, n3, n4, n5, n6) = (n.powi(2), n.powi(3), n.powi(4), n.powi(5), n.powi(6));
+        let rectifying_radius = ellipsoid.get_semi_major_axis() / (1.0 + n) * (1.0 + n2 / 4.0 + n4 / 64.0 + n6 / 256.0);
+        let alpha = [n / 2.0 - 2.0 / 3.0 * n2 + 5.0 / 16.0 * n3 + 41.0 / 180.0 * n4 - 127.0 / 288.0 * n5 + 7891.0 / 37800.0 * n6,
+                     13.0 / 48.0 * n2 - 3.0 / 5.0 * n3 + 557.0 / 1440.0 * n4 + 281.0 / 630.0 * n5 - 1983433.0 / 1935360.0 * n6,
+                     61.0 / 240.0 * n3 - 103.0 / 140.0 * n4 + 15061.0 / 26880.0 * n5 + 167603.0 / 181440.0 * n6,
+                     49561.0 / 161280.0 * n4 - 179.0 / 168.0 * n5 + 6601661.0 / 7257600.0 * n6,
+                     34729.0 / 80640.0 * n5 - 3418889.0 / 1995840.0 * n6,
+                     212378941.0 / 319334400.0 * n6];
+        let beta = [n / 2.0 - 2.0 / 3.0 * n2 + 37.0 / 96.0 * n3 - 1.0 / 360.0 * n4 - 81.0 / 512.0 * n5 + 96199.0 / 604800.0 * n6,
+                    n2 / 48.0 + n3 / 15.0 - 437.0 / 1440.0 * n4 + 46.0 / 105.0 * n5 - 1118711.0 / 3870720.0 * n6,
+                    17.0 / 480.0 * n3 - 37.0 / 840.0 * n4 - 209.0 / 4480.0 * n5 + 5569.0 / 90720.0 * n6,
+                    4397.0 / 161280.0 * n4 - 11.0 / 504.0 * n5 - 830251.0 / 7257600.0 * n6,
+                    4583.0 / 161280.0 * n5 - 108847.0 / 3991680.0 * n6,
+                    20648693.0 / 638668800.0 * n6];
+        let mut tm = TransverseMercator {
+            lat0,
+            lon0,
+            scale_factor,
+            false_easting,
+            false_northing,
+            ecc: ellipsoid.get_first_ecc(),
+            semi_major_axis: ellipsoid.get_semi_major_axis(),
+            rectifying_radius,
+            alpha,
+            beta,
+            origin_northing: 0.0,
+            definition: Definition { lat0, lon0, scale_factor, false_easting, false_northing, ellipsoid: *ellipsoid }
+        };
+        tm.origin_northing = tm.unit_forward(lat0, 0.0).1;
+        tm
+    }
+
+    pub fn get_lat0(&self) -> f64 {
+        self.lat0
+    }
+
+    pub fn get_lon0(&self) -> f64 {
+        self.lon0
+    }
+
+    pub fn get_scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    pub fn get_false_easting(&self) -> f64 {
+        self.false_easting
+    }
+
+    pub fn get_false_northing(&self) -> f64 {
+        self.false_northing
+    }
+
+    /// Converts 3-d LLA coordinates to projected coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - easting, northing, altitude (meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * tau' = conformal latitude tangent, xi' = atan2(tau', cos(dlon)), eta' = asinh(sin(dlon) / sqrt(tau'^2 + cos(dlon)^2))
+    /// * xi = xi' + sum(alpha_j * sin(2j * xi') * cosh(2j * eta')), eta = eta' + sum(alpha_j * cos(2j * xi') * sinh(2j * eta'))
+    /// * easting = FE + k0 * A * eta, northing = FN + k0 * (A * xi - M0)
+    pub fn forward(&self, lla_vec: &Vector3<f64>) -> Vector3<f64> {
+        let (x, y) = self.unit_forward(lla_vec.x, geo::wrap_longitude(lla_vec.y - self.lon0));
+        Vector3::new(self.false_easting + self.scale_factor * x,
+                     self.false_northing + self.scale_factor * (y - self.origin_northing),
+                     lla_vec.z)
+    }
+
+    /// Converts projected coordinates to 3-d LLA coordinates
+    /// 
+    /// # Arguments
+    /// 
+    /// * `proj_vec` - Vector3 reference to the projected vector (easting, northing, altitude) (meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * nalgebra::Vector3<f64> - lat, long, alt (radians, radians, meters)
+    /// 
+    /// # Formula
+    /// 
+    /// * xi = ((northing - FN) / k0 + M0) / A, eta = (easting - FE) / (k0 * A)
+    /// * xi' = xi - sum(beta_j * sin(2j * xi) * cosh(2j * eta)), eta' = eta - sum(beta_j * cos(2j * xi) * sinh(2j * eta))
+    /// * lat from tau' = sin(xi') / sqrt(sinh(eta')^2 + cos(xi')^2) by Newton's method, dlon = atan2(sinh(eta'), cos(xi'))
+    pub fn inverse(&self, proj_vec: &Vector3<f64>) -> Vector3<f64> {
+        let x = (proj_vec.x - self.false_easting) / self.scale_factor;
+        let y = (proj_vec.y - self.false_northing) / self.scale_factor + self.origin_northing;
+        let (lat, dlon) = self.unit_inverse(x, y);
+        Vector3::new(lat, geo::wrap_longitude(self.lon0 + dlon), proj_vec.z)
+    }
+
+    /// Computes the meridian convergence and point scale factor
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lla_vec` - Vector3 reference to the LLA vector (latitude, longitude, altitude) (radians, radians, meters)
+    /// 
+    /// # Return Value
+    /// 
+    /// * (f64, f64) - convergence, the angle from true north to grid north measured clockwise (radians), and point scale factor
+    /// 
+    /// # Formula
+    /// 
+    /// * p = 1 + sum(2j * alpha_j * cos(2j * xi') * cosh(2j * eta')), q = sum(2j * alpha_j * sin(2j * xi') * sinh(2j * eta'))
+    /// * convergence = atan(tan(xi') * tanh(eta')) + atan2(q, p)
+    /// * scale = k0 * (A / a) * sqrt(p^2 + q^2) * sqrt(1 - e^2 * sin(lat)^2) * sqrt(1 + tan(lat)^2) / sqrt(tau'^2 + cos(dlon)^2)
+    pub fn convergence_and_scale(&self, lla_vec: &Vector3<f64>) -> (f64, f64) {
+        let e = self.ecc;
+        let dlon = geo::wrap_longitude(lla_vec.y - self.lon0);
+        let tau = lla_vec.x.tan();
+        let sigma = (e * (e * tau / (1.0 + tau.powi(2)).sqrt()).atanh()).sinh();
+        let tau_prime = tau * (1.0 + sigma.powi(2)).sqrt() - sigma * (1.0 + tau.powi(2)).sqrt();
+        let xi_prime = tau_prime.atan2(dlon.cos());
+        let eta_prime = (dlon.sin() / (tau_prime.powi(2) + dlon.cos().powi(2)).sqrt()).asinh();
+        let mut p = 1.0;
+        let mut q = 0.0;
+        for (j, alpha_j) in self.alpha.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            p += k * alpha_j * (k * xi_prime).cos() * (k * eta_prime).cosh();
+            q += k * alpha_j * (k * xi_prime).sin() * (k * eta_prime).sinh();
+        }
+        let convergence = (xi_prime.tan() * eta_prime.tanh()).atan() + q.atan2(p);
+        let sphere_scale = (1.0 - (e * lla_vec.x.sin()).powi(2)).sqrt() * (1.0 + tau.powi(2)).sqrt()
+            / (tau_prime.powi(2) + dlon.cos().powi(2)).sqrt();
+        (convergence, self.scale_factor * self.rectifying_radius / self.semi_major_axis * p.hypot(q) * sphere_scale)
+    }
+
+    /// Unit-scale (x, y) in meters from the equator for a latitude and a longitude relative to the central meridian
+    fn unit_forward(&self, lat: f64, dlon: f64) -> (f64, f64) {
+        let e = self.ecc;
+        let tau = lat.tan();
+        let sigma = (e * (e * tau / (1.0 + tau.powi(2)).sqrt()).atanh()).sinh();
+        let tau_prime = tau * (1.0 + sigma.powi(2)).sqrt() - sigma * (1.0 + tau.powi(2)).sqrt();
+        let xi_prime = tau_prime.atan2(dlon.cos());
+        let eta_prime = (dlon.sin() / (tau_prime.powi(2) + dlon.cos().powi(2)).sqrt()).asinh();
+        let mut xi = xi_prime;
+        let mut eta = eta_prime;
+        for (j, alpha_j) in self.alpha.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            xi += alpha_j * (k * xi_prime).sin() * (k * eta_prime).cosh();
+            eta += alpha_j * (k * xi_prime).cos() * (k * eta_prime).sinh();
+        }
+        (self.rectifying_radius * eta, self.rectifying_radius * xi)
+    }
+
+    /// Latitude and longitude relative to the central meridian for unit-scale (x, y) in meters from the equator
+    fn unit_inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let e = self.ecc;
+        let e2 = e.powi(2);
+        let xi = y / self.rectifying_radius;
+        let eta = x / self.rectifying_radius;
+        let mut xi_prime = xi;
+        let mut eta_prime = eta;
+        for (j, beta_j) in self.beta.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            xi_prime -= beta_j * (k * xi).sin() * (k * eta).cosh();
+            eta_prime -= beta_j * (k * xi).cos() * (k * eta).sinh();
+        }
+        let tau_prime = xi_prime.sin() / (eta_prime.sinh().powi(2) + xi_prime.cos().powi(2)).sqrt();
+        let mut tau = tau_prime;
+        for _ in 0..10 {
+            let sigma = (e * (e * tau / (1.0 + tau.powi(2)).sqrt()).atanh()).sinh();
+            let tau_i = tau * (1.0 + sigma.powi(2)).sqrt() - sigma * (1.0 + tau.powi(2)).sqrt();
+            let delta = (tau_prime - tau_i) / (1.0 + tau_i.powi(2)).sqrt()
+                * (1.0 + (1.0 - e2) * tau.powi(2)) / ((1.0 - e2) * (1.0 + tau.powi(2)).sqrt());
+            tau += delta;
+            if delta.abs() < 1.0e-12 {
+                break;
+            }
+        }
+        (tau.atan(), eta_prime.sinh().atan2(xi_prime.cos()))
+    }
+}
+
+impl Projection for TransverseMercator {
+    fn project(&self, lla_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.forward(lla_vec))
+    }
+
+    fn unproject(&self, proj_vec: &Vector3<f64>) -> Result<Vector3<f64>, GeoError> {
+        Ok(self.inverse(proj_vec))
+    }
+}
+
+/// Serializes the constructor arguments only
+#[cfg(feature = "serde")]
+impl Serialize for TransverseMercator {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.definition.serialize(serializer)
+    }
+}
+
+/// Rebuilds the derived values through the constructor, the ellipsoid is validated by its own deserializer
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TransverseMercator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TransverseMercator, D::Error> {
+        let definition = Definition::deserialize(deserializer)?;
+        Ok(TransverseMercator::new(definition.lat0, definition.lon0, definition.scale_factor, definition.false_easting,
+                                   definition.false_northing, &definition.ellipsoid))
+    }
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_forward() {
+        //EPSG guidance note 7-2 example, OSGB 1936 / British National Grid on the Airy 1830 ellipsoid
+        let airy = geo_ellipsoid::GeoEllipsoid::new(6377563.396, 299.3249646);
+        let tm = TransverseMercator::new(49.0f64.to_radians(), -2.0f64.to_radians(), 0.9996012717,
+                                         400000.0, -100000.0, &airy);
+        let proj_vec = tm.forward(&Vector3::new(50.5f64.to_radians(), 0.5f64.to_radians(), 0.0));
+        assert_approx_eq!(proj_vec.x, 577274.99, 0.01);
+        assert_approx_eq!(proj_vec.y, 69740.50, 0.01);
+        //The natural origin maps to the false offsets
+        let origin = tm.forward(&Vector3::new(49.0f64.to_radians(), -2.0f64.to_radians(), 7.0));
+        assert_approx_eq!(origin.x, 400000.0, 1e-9);
+        assert_approx_eq!(origin.y, -100000.0, 1e-9);
+        assert_eq!(origin.z, 7.0);
+    }
+    #[test]
+    fn test_inverse() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let tm = TransverseMercator::new(-30.0f64.to_radians(), 24.0f64.to_radians(), 0.9999, 250000.0, 1000000.0, &ellipsoid);
+        //Round trips to well below a millimeter within 10 degrees of the central meridian
+        for lat_deg in [-85.0f64, -60.0, -30.0, 0.0, 12.5, 45.0, 80.0].iter() {
+            for dlon_deg in [-10.0f64, -3.0, 0.0, 0.5, 6.0, 10.0].iter() {
+                let lla_vec = Vector3::new(lat_deg.to_radians(), (24.0 + dlon_deg).to_radians(), 50.0);
+                let back = tm.inverse(&tm.forward(&lla_vec));
+                assert_approx_eq!(back.x, lla_vec.x, 1e-12);
+                assert_approx_eq!(back.y, lla_vec.y, 1e-12);
+                assert_eq!(back.z, 50.0);
+            }
+        }
+        let batch = tm.project_batch(&[Vector3::new(0.0, 0.4, 0.0), Vector3::new(-0.5, 0.45, 0.0)]).unwrap();
+        let back = tm.unproject_batch(&batch).unwrap();
+        assert_approx_eq!(back[1].x, -0.5, 1e-12);
+        assert_approx_eq!(back[1].y, 0.45, 1e-12);
+    }
+    #[test]
+    fn test_convergence_and_scale() {
+        let ellipsoid = geo_ellipsoid::GeoEllipsoid::new(geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+                                            geo_ellipsoid::WGS84_FLATTENING);
+        let tm = TransverseMercator::new(0.0, 9.0f64.to_radians(), 0.9996, 500000.0, 0.0, &ellipsoid);
+        //No convergence and the central scale factor on the central meridian
+        for lat_deg in [-70.0f64, -20.0, 0.0, 35.0, 80.0].iter() {
+            let (convergence, scale) = tm.convergence_and_scale(&Vector3::new(lat_deg.to_radians(), 9.0f64.to_radians(), 0.0));
+            assert_approx_eq!(convergence, 0.0, 1e-15);
+            assert_approx_eq!(scale, 0.9996, 1e-15);
+        }
+        //3 degrees off the central meridian against the Redfearn series, truncated after the fourth-order terms
+        let e2 = ellipsoid.get_first_ecc_squared();
+        let dlon = 3.0f64.to_radians();
+        for lat_deg in [-60.0f64, 0.0, 30.0, 45.0, 70.0].iter() {
+            let lat = lat_deg.to_radians();
+            let (c, t) = (lat.cos(), lat.tan());
+            let eta2 = e2 / (1.0 - e2) * c.powi(2);
+            let l2 = (dlon * c).powi(2);
+            let redfearn_convergence = dlon * lat.sin() * (1.0 + l2 / 3.0 * (1.0 + 3.0 * eta2 + 2.0 * eta2.powi(2)) + l2.powi(2) / 15.0 * (2.0 - t.powi(2)));
+            let redfearn_scale = 0.9996 * (1.0 + l2 / 2.0 * (1.0 + eta2) + l2.powi(2) / 24.0 * (5.0 - 4.0 * t.powi(2) + 14.0 * eta2 - 28.0 * t.powi(2) * eta2));
+            let (convergence, scale) = tm.convergence_and_scale(&Vector3::new(lat, 12.0f64.to_radians(), 0.0));
+            assert_approx_eq!(convergence, redfearn_convergence, 1e-8);
+            assert_approx_eq!(scale, redfearn_scale, 1e-8);
+            //Convergence is odd in the longitude difference
+            let (west, _) = tm.convergence_and_scale(&Vector3::new(lat, 6.0f64.to_radians(), 0.0));
+            assert_approx_eq!(west, -convergence, 1e-15);
+        }
+        //The UTM zone edge on the equator has a scale of about 1.00098
+        let (_, scale) = tm.convergence_and_scale(&Vector3::new(0.0, 12.0f64.to_radians(), 0.0));
+        assert_approx_eq!(scale, 1.000_98, 1e-5);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json;
+        let tm = TransverseMercator::new(0.5, -0.1, 0.9996, 500000.0, -100000.0, &geo_ellipsoid::GeoEllipsoid::airy_1830());
+        let json = serde_json::to_string(&tm).unwrap();
+        assert!(json.starts_with(r#"{"lat0":0.5,"lon0":-0.1,"scale_factor":0.9996,"#));
+        let back: TransverseMercator = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, tm);
+        assert!(serde_json::from_str::<TransverseMercator>(&json.replace("6377563.396", "-1.0")).is_err());
+    }
+}
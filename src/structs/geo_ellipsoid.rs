@@ -1,3 +1,8 @@
+use std::fmt;
+use std::error::Error;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
 pub const WGS84_SEMI_MAJOR_AXIS_METERS: f64 = 6378137.0;
 pub const WGS84_FLATTENING: f64 = 298.257223563;
 pub const WGS72_SEMI_MAJOR_AXIS_METERS: f64 = 6378135.0;
@@ -44,27 +49,290 @@ pub const AUSTRALIAN_NATIONAL_SEMI_MAJOR_AXIS_METERS: f64 = 6378160.0;
 pub const AUSTRALIAN_NATIONAL_FLATTENING: f64 = 298.25;
 pub const AIRY_SEMI_MAJOR_AXIS_METERS: f64 = 6377563.396;
 pub const AIRY_FLATTENING: f64 = 299.3249646;
+pub const MEAN_EARTH_RADIUS_METERS: f64 = 6371008.8;
+pub const GRS_1980_AUTHALIC_SPHERE_SEMI_MAJOR_AXIS_METERS: f64 = 6371007.0;
+pub const GRS_1980_AUTHALIC_SPHERE_FLATTENING: f64 = f64::INFINITY;
+pub const MOON_SEMI_MAJOR_AXIS_METERS: f64 = 1737400.0;
+pub const MOON_FLATTENING: f64 = f64::INFINITY;
+pub const MARS_SEMI_MAJOR_AXIS_METERS: f64 = 3396190.0;
+pub const MARS_FLATTENING: f64 = 3396190.0 / (3396190.0 - 3376200.0);
+pub const VENUS_SEMI_MAJOR_AXIS_METERS: f64 = 6051800.0;
+pub const VENUS_FLATTENING: f64 = f64::INFINITY;
+pub const MERCURY_SEMI_MAJOR_AXIS_METERS: f64 = 2440530.0;
+pub const MERCURY_FLATTENING: f64 = 2440530.0 / (2440530.0 - 2438260.0);
+pub const JUPITER_SEMI_MAJOR_AXIS_METERS: f64 = 71492000.0;
+pub const JUPITER_FLATTENING: f64 = 71492000.0 / (71492000.0 - 66854000.0);
+pub const SATURN_SEMI_MAJOR_AXIS_METERS: f64 = 60268000.0;
+pub const SATURN_FLATTENING: f64 = 60268000.0 / (60268000.0 - 54364000.0);
+pub const URANUS_SEMI_MAJOR_AXIS_METERS: f64 = 25559000.0;
+pub const URANUS_FLATTENING: f64 = 25559000.0 / (25559000.0 - 24973000.0);
+pub const NEPTUNE_SEMI_MAJOR_AXIS_METERS: f64 = 24764000.0;
+pub const NEPTUNE_FLATTENING: f64 = 24764000.0 / (24764000.0 - 24341000.0);
+
+/// PROJ ellipsoid names (+ellps=) with their semi-major axis (meters) and inverse flattening
+pub const PROJ_ELLIPSOIDS: [(&str, f64, f64); 22] = [
+	("WGS84", WGS84_SEMI_MAJOR_AXIS_METERS, WGS84_FLATTENING),
+	("WGS72", WGS72_SEMI_MAJOR_AXIS_METERS, WGS72_FLATTENING),
+	("WGS66", WGS66_SEMI_MAJOR_AXIS_METERS, WGS66_FLATTENING),
+	("WGS60", WGS60_SEMI_MAJOR_AXIS_METERS, WGS60_FLATTENING),
+	("GRS80", GRS_1980_SEMI_MAJOR_AXIS_METERS, GRS_1980_FLATTENING),
+	("GRS67", GRS_1967_SEMI_MAJOR_AXIS_METERS, GRS_1967_FLATTENING),
+	("airy", AIRY_SEMI_MAJOR_AXIS_METERS, AIRY_FLATTENING),
+	("mod_airy", AIRY_MODIFIED_SEMI_MAJOR_AXIS_METERS, AIRY_MODIFIED_FLATTENING),
+	("bessel", BESSEL_1841_SEMI_MAJOR_AXIS_METERS, BESSEL_1841_FLATTENING),
+	("bess_nam", BESSEL_1841_NAMBIA_SEMI_MAJOR_AXIS_METERS, BESSEL_1841_NAMBIA_FLATTENING),
+	("clrk66", CLARKE_1866_SEMI_MAJOR_AXIS_METERS, CLARKE_1866_FLATTENING),
+	("clrk80", CLARKE_1880_SEMI_MAJOR_AXIS_METERS, CLARKE_1880_FLATTENING),
+	("intl", INTERNATIONAL_SEMI_MAJOR_AXIS_METERS, INTERNATIONAL_FLATTENING),
+	("krass", KRASSOVSKY_SEMI_MAJOR_AXIS_METERS, KRASSOVSKY_FLATTENING),
+	("evrst30", EVEREST_SEMI_MAJOR_AXIS_METERS, EVEREST_FLATTENING),
+	("evrst48", EVEREST_MODIFIED_SEMI_MAJOR_AXIS_METERS, EVEREST_MODIFIED_FLATTENING),
+	("helmert", HELMERT_1906_SEMI_MAJOR_AXIS_METERS, HELMERT_1906_FLATTENING),
+	("hough", HOUGH_SEMI_MAJOR_AXIS_METERS, HOUGH_FLATTENING),
+	("fschr60", FISCHER_1960_MERCURY_SEMI_MAJOR_AXIS_METERS, FISCHER_1960_MERCURY_FLATTENING),
+	("fschr60m", FISCHER_1969_MODIFIED_SEMI_MAJOR_AXIS_METERS, FISCHER_1969_MODIFIED_FLATTENING),
+	("fschr68", FISCHER_1968_SEMI_MAJOR_AXIS_METERS, FISCHER_1968_FLATTENING),
+	("aust_SA", AUSTRALIAN_NATIONAL_SEMI_MAJOR_AXIS_METERS, AUSTRALIAN_NATIONAL_FLATTENING)
+];
+
+/// Descriptive ellipsoid names, without spaces, hyphens or underscores, with the PROJ name they stand for
+const ELLIPSOID_ALIASES: [(&str, &str); 20] = [
+	("wgs1984", "WGS84"),
+	("wgs1972", "WGS72"),
+	("grs1980", "GRS80"),
+	("grs1967", "GRS67"),
+	("airy1830", "airy"),
+	("airymodified", "mod_airy"),
+	("modifiedairy", "mod_airy"),
+	("bessel1841", "bessel"),
+	("clarke1866", "clrk66"),
+	("clarke1880", "clrk80"),
+	("international", "intl"),
+	("international1924", "intl"),
+	("hayford", "intl"),
+	("krassovsky", "krass"),
+	("krassowsky", "krass"),
+	("krassovsky1940", "krass"),
+	("krassowsky1940", "krass"),
+	("everest", "evrst30"),
+	("everest1830", "evrst30"),
+	("helmert1906", "helmert")
+];
+
+/// Errors returned by the validating ellipsoid constructor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EllipsoidError {
+	/// The semi-major axis or the flattening is NaN or infinite
+	NonFiniteInput,
+	/// The semi-major axis is zero or negative
+	NonPositiveSemiMajorAxis,
+	/// The flattening lies outside [0, 1)
+	FlatteningOutOfRange
+}
+
+impl fmt::Display for EllipsoidError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			EllipsoidError::NonFiniteInput => write!(f, "semi-major axis and flattening must be finite"),
+			EllipsoidError::NonPositiveSemiMajorAxis => write!(f, "semi-major axis must be positive"),
+			EllipsoidError::FlatteningOutOfRange => write!(f, "flattening must lie in [0, 1)")
+		}
+	}
+}
 
-pub struct geo_ellipsoid {
+impl Error for EllipsoidError {}
+
+/// Reference ellipsoid defined by its semi-major axis and flattening
+#[derive(Debug, Clone, Copy)]
+pub struct GeoEllipsoid {
 	semi_major_axis: f64,
 	flattening: f64,
 	semi_minor_axis: f64,
 	first_ecc: f64,
-	second_ecc: f64
+	second_ecc: f64,
+	first_ecc_squared: f64,
+	second_ecc_squared: f64,
+	axis_ratio_squared: f64,
+	one_minus_ecc_squared: f64
 }
 
-impl geo_ellipsoid {
-	pub fn new(sma: f64, f: f64) -> geo_ellipsoid {
+impl GeoEllipsoid {
+	/// Builds an ellipsoid from its semi-major axis (meters) and inverse flattening, without validation
+	///
+	/// A sphere takes an infinite inverse flattening. Invalid parameters, such as a negative axis or an
+	/// inverse flattening below 1, are accepted and give NaN eccentricities; try_from_inverse_flattening
+	/// checks them with the same arguments. Note that try_new takes the flattening, not its inverse.
+	pub fn new(sma: f64, f: f64) -> GeoEllipsoid {
 		let smia = sma * (1.0 - (1.0 / f));
-		geo_ellipsoid {
+		GeoEllipsoid::with_axes(sma, 1.0 / f, smia)
+	}
+
+	/// Builds an ellipsoid from its semi-major axis (meters) and flattening, not the inverse flattening
+	/// taken by new
+	///
+	/// Rejects non-finite inputs, a semi-major axis <= 0 and a flattening outside [0, 1); f = 0 gives a sphere.
+	pub fn try_new(sma: f64, flattening: f64) -> Result<GeoEllipsoid, EllipsoidError> {
+		if !(sma.is_finite() && flattening.is_finite()) {
+			return Err(EllipsoidError::NonFiniteInput);
+		}
+		if sma <= 0.0 {
+			return Err(EllipsoidError::NonPositiveSemiMajorAxis);
+		}
+		if !(0.0..1.0).contains(&flattening) {
+			return Err(EllipsoidError::FlatteningOutOfRange);
+		}
+		Ok(GeoEllipsoid::with_axes(sma, flattening, sma * (1.0 - flattening)))
+	}
+
+	/// Builds an ellipsoid from its semi-major axis (meters) and inverse flattening, the arguments of new
+	///
+	/// Applies the checks of try_new to f = 1 / inverse_flattening, so an infinite inverse flattening gives
+	/// a sphere and inverse flattenings of 1 or below are rejected.
+	pub fn try_from_inverse_flattening(sma: f64, inverse_flattening: f64) -> Result<GeoEllipsoid, EllipsoidError> {
+		//1 / 0 would be reported as a non-finite flattening rather than one out of range
+		let flattening = if inverse_flattening == 0.0 { 1.0 } else { 1.0 / inverse_flattening };
+		GeoEllipsoid::try_new(sma, flattening)
+	}
+
+	/// Builds an ellipsoid from its semi-major and semi-minor axes (meters)
+	///
+	/// Only oblate ellipsoids (semi_minor <= semi_major) are supported; for semi_minor > semi_major the
+	/// flattening is negative and the eccentricities are NaN.
+	pub fn from_axes(semi_major: f64, semi_minor: f64) -> GeoEllipsoid {
+		GeoEllipsoid::with_axes(semi_major, (semi_major - semi_minor) / semi_major, semi_minor)
+	}
+
+	/// Derives the eccentricities and the cached powers once from the axes and the flattening
+	fn with_axes(sma: f64, flattening: f64, smia: f64) -> GeoEllipsoid {
+		let first_ecc = ((sma.powi(2) - smia.powi(2)) / (sma.powi(2))).sqrt();
+		let second_ecc = ((sma.powi(2) - smia.powi(2)) / (smia.powi(2))).sqrt();
+		GeoEllipsoid {
 			semi_major_axis: sma,
-			flattening: 1.0 / f,
+			flattening,
 			semi_minor_axis: smia,
-			first_ecc: ((sma.powi(2) - smia.powi(2)) / (sma.powi(2))).sqrt(),
-			second_ecc: ((sma.powi(2) - smia.powi(2)) / (smia.powi(2))).sqrt(),
+			first_ecc,
+			second_ecc,
+			first_ecc_squared: first_ecc.powi(2),
+			second_ecc_squared: second_ecc.powi(2),
+			axis_ratio_squared: smia.powi(2) / sma.powi(2),
+			one_minus_ecc_squared: 1.0 - first_ecc.powi(2),
 		}
 	}
 
+	/// Looks up an ellipsoid by its PROJ name (+ellps=) or a descriptive alias such as "GRS 1980" or "Bessel 1841"
+	///
+	/// Matching ignores case, spaces, hyphens and underscores. Unknown names give None; PROJ_ELLIPSOIDS lists
+	/// the supported PROJ names.
+	pub fn from_name(name: &str) -> Option<GeoEllipsoid> {
+		let normalize = |name: &str| name.chars().filter(|c| !matches!(c, ' ' | '-' | '_')).collect::<String>().to_lowercase();
+		let key = normalize(name);
+		let proj_name = ELLIPSOID_ALIASES.iter().find(|alias| alias.0 == key).map(|alias| alias.1);
+		PROJ_ELLIPSOIDS.iter()
+			.find(|ellipsoid| proj_name.map_or(normalize(ellipsoid.0) == key, |proj_name| ellipsoid.0 == proj_name))
+			.map(|&(_, a, inverse_flattening)| GeoEllipsoid::new(a, inverse_flattening))
+	}
+
+	/// WGS 84 (EPSG:7030)
+	pub fn wgs84() -> GeoEllipsoid {
+		GeoEllipsoid::new(WGS84_SEMI_MAJOR_AXIS_METERS, WGS84_FLATTENING)
+	}
+
+	/// GRS 1980 (EPSG:7019)
+	pub fn grs80() -> GeoEllipsoid {
+		GeoEllipsoid::new(GRS_1980_SEMI_MAJOR_AXIS_METERS, GRS_1980_FLATTENING)
+	}
+
+	/// WGS 72 (EPSG:7043)
+	pub fn wgs72() -> GeoEllipsoid {
+		GeoEllipsoid::new(WGS72_SEMI_MAJOR_AXIS_METERS, WGS72_FLATTENING)
+	}
+
+	/// Airy 1830 (EPSG:7001)
+	pub fn airy_1830() -> GeoEllipsoid {
+		GeoEllipsoid::new(AIRY_SEMI_MAJOR_AXIS_METERS, AIRY_FLATTENING)
+	}
+
+	/// Airy Modified 1849 (EPSG:7002)
+	pub fn airy_modified() -> GeoEllipsoid {
+		GeoEllipsoid::new(AIRY_MODIFIED_SEMI_MAJOR_AXIS_METERS, AIRY_MODIFIED_FLATTENING)
+	}
+
+	/// Bessel 1841 (EPSG:7004)
+	pub fn bessel_1841() -> GeoEllipsoid {
+		GeoEllipsoid::new(BESSEL_1841_SEMI_MAJOR_AXIS_METERS, BESSEL_1841_FLATTENING)
+	}
+
+	/// Clarke 1866 (EPSG:7008)
+	pub fn clarke_1866() -> GeoEllipsoid {
+		GeoEllipsoid::new(CLARKE_1866_SEMI_MAJOR_AXIS_METERS, CLARKE_1866_FLATTENING)
+	}
+
+	/// Clarke 1880 (RGS) (EPSG:7012)
+	pub fn clarke_1880() -> GeoEllipsoid {
+		GeoEllipsoid::new(CLARKE_1880_SEMI_MAJOR_AXIS_METERS, CLARKE_1880_FLATTENING)
+	}
+
+	/// International 1924 (EPSG:7022)
+	pub fn international_1924() -> GeoEllipsoid {
+		GeoEllipsoid::new(INTERNATIONAL_SEMI_MAJOR_AXIS_METERS, INTERNATIONAL_FLATTENING)
+	}
+
+	/// Krassowsky 1940 (EPSG:7024)
+	pub fn krassovsky_1940() -> GeoEllipsoid {
+		GeoEllipsoid::new(KRASSOVSKY_SEMI_MAJOR_AXIS_METERS, KRASSOVSKY_FLATTENING)
+	}
+
+	/// GRS 1980 Authalic Sphere (EPSG:7048)
+	pub fn authalic_sphere() -> GeoEllipsoid {
+		GeoEllipsoid::new(GRS_1980_AUTHALIC_SPHERE_SEMI_MAJOR_AXIS_METERS, GRS_1980_AUTHALIC_SPHERE_FLATTENING)
+	}
+
+	/// Moon, IAU 2015 mean radius sphere
+	pub fn moon() -> GeoEllipsoid {
+		GeoEllipsoid::new(MOON_SEMI_MAJOR_AXIS_METERS, MOON_FLATTENING)
+	}
+
+	/// Mars, IAU 2015 (MOLA) ellipsoid with a = 3396190 m, b = 3376200 m
+	pub fn mars() -> GeoEllipsoid {
+		GeoEllipsoid::new(MARS_SEMI_MAJOR_AXIS_METERS, MARS_FLATTENING)
+	}
+
+	/// Venus, IAU 2015 sphere
+	pub fn venus() -> GeoEllipsoid {
+		GeoEllipsoid::new(VENUS_SEMI_MAJOR_AXIS_METERS, VENUS_FLATTENING)
+	}
+
+	/// Mercury, IAU 2015 equatorial and polar radii a = 2440530 m, b = 2438260 m
+	pub fn mercury() -> GeoEllipsoid {
+		GeoEllipsoid::new(MERCURY_SEMI_MAJOR_AXIS_METERS, MERCURY_FLATTENING)
+	}
+
+	/// Jupiter, IAU 2015 1 bar level, a = 71492 km, b = 66854 km
+	///
+	/// The single Bowring step of geo::ecef2lla loses accuracy on this flattening, about 0.4 m of latitude at
+	/// 250 km altitude and 140 m at 5000 km; use geo::ecef2lla_exact or geo::ecef2lla_iterative off the surface.
+	pub fn jupiter() -> GeoEllipsoid {
+		GeoEllipsoid::new(JUPITER_SEMI_MAJOR_AXIS_METERS, JUPITER_FLATTENING)
+	}
+
+	/// Saturn, IAU 2015 1 bar level, a = 60268 km, b = 54364 km
+	///
+	/// The single Bowring step of geo::ecef2lla loses accuracy on this flattening, about 1.9 m of latitude at
+	/// 250 km altitude and 590 m at 5000 km; use geo::ecef2lla_exact or geo::ecef2lla_iterative off the surface.
+	pub fn saturn() -> GeoEllipsoid {
+		GeoEllipsoid::new(SATURN_SEMI_MAJOR_AXIS_METERS, SATURN_FLATTENING)
+	}
+
+	/// Uranus, IAU 2015 1 bar level, a = 25559 km, b = 24973 km
+	pub fn uranus() -> GeoEllipsoid {
+		GeoEllipsoid::new(URANUS_SEMI_MAJOR_AXIS_METERS, URANUS_FLATTENING)
+	}
+
+	/// Neptune, IAU 2015 1 bar level, a = 24764 km, b = 24341 km
+	pub fn neptune() -> GeoEllipsoid {
+		GeoEllipsoid::new(NEPTUNE_SEMI_MAJOR_AXIS_METERS, NEPTUNE_FLATTENING)
+	}
+
 	pub fn get_semi_major_axis(&self) -> f64 {
 		self.semi_major_axis
 	}
@@ -84,4 +352,375 @@ impl geo_ellipsoid {
 	pub fn get_second_ecc(&self) -> f64 {
 		self.second_ecc
 	}
+
+	/// Square of the first eccentricity, e^2
+	pub fn get_first_ecc_squared(&self) -> f64 {
+		self.first_ecc_squared
+	}
+
+	/// Square of the second eccentricity, e'^2
+	pub fn get_second_ecc_squared(&self) -> f64 {
+		self.second_ecc_squared
+	}
+
+	/// Squared axis ratio, b^2 / a^2
+	pub fn get_axis_ratio_squared(&self) -> f64 {
+		self.axis_ratio_squared
+	}
+
+	/// 1 - e^2
+	pub fn get_one_minus_ecc_squared(&self) -> f64 {
+		self.one_minus_ecc_squared
+	}
+
+	/// Third flattening n = (a - b) / (a + b)
+	pub fn get_third_flattening(&self) -> f64 {
+		self.flattening / (2.0 - self.flattening)
+	}
+
+	/// Arithmetic mean radius R1 = (2a + b) / 3 (meters)
+	pub fn get_mean_radius(&self) -> f64 {
+		(2.0 * self.semi_major_axis + self.semi_minor_axis) / 3.0
+	}
+
+	/// Authalic radius R2 = a * sqrt((1 + (1 - e^2) * atanh(e) / e) / 2), the radius of the sphere of equal surface area (meters)
+	pub fn get_authalic_radius(&self) -> f64 {
+		if self.first_ecc == 0.0 {
+			self.semi_major_axis
+		} else {
+			self.semi_major_axis * ((1.0 + self.one_minus_ecc_squared * self.first_ecc.atanh() / self.first_ecc) / 2.0).sqrt()
+		}
+	}
+
+	/// Meridional radius of curvature M = a * (1 - e^2) / (1 - e^2 * sin^2(lat))^(3/2) at a geodetic latitude (radians) (meters)
+	pub fn radius_meridional(&self, lat: f64) -> f64 {
+		let w2 = 1.0 - self.first_ecc_squared * lat.sin().powi(2);
+		self.radius_prime_vertical(lat) * self.one_minus_ecc_squared / w2
+	}
+
+	/// Prime vertical radius of curvature N = a / sqrt(1 - e^2 * sin^2(lat)) at a geodetic latitude (radians) (meters)
+	pub fn radius_prime_vertical(&self, lat: f64) -> f64 {
+		self.semi_major_axis / (1.0 - self.first_ecc_squared * lat.sin().powi(2)).sqrt()
+	}
+
+	/// Gaussian mean radius of curvature sqrt(M * N) at a geodetic latitude (radians) (meters)
+	pub fn radius_gaussian(&self, lat: f64) -> f64 {
+		(self.radius_meridional(lat) * self.radius_prime_vertical(lat)).sqrt()
+	}
+
+	/// Radius of curvature of the normal section in an azimuth clockwise from north (radians) at a geodetic latitude (radians),
+	/// by Euler's formula 1 / R = cos^2(az) / M + sin^2(az) / N (meters)
+	pub fn radius_in_azimuth(&self, lat: f64, azimuth: f64) -> f64 {
+		1.0 / (azimuth.cos().powi(2) / self.radius_meridional(lat) + azimuth.sin().powi(2) / self.radius_prime_vertical(lat))
+	}
+
+	/// Distance from the geocenter to the surface at a geodetic latitude (radians),
+	/// sqrt(((a^2 * cos(lat))^2 + (b^2 * sin(lat))^2) / ((a * cos(lat))^2 + (b * sin(lat))^2)) (meters)
+	pub fn geocentric_radius(&self, lat: f64) -> f64 {
+		let (sin_lat, cos_lat) = lat.sin_cos();
+		let (a, b) = (self.semi_major_axis, self.semi_minor_axis);
+		(((a * a * cos_lat).powi(2) + (b * b * sin_lat).powi(2)) / ((a * cos_lat).powi(2) + (b * sin_lat).powi(2))).sqrt()
+	}
+
+	/// Volumetric radius R3 = (a^2 * b)^(1/3), the radius of the sphere of equal volume (meters)
+	pub fn get_volumetric_radius(&self) -> f64 {
+		(self.semi_major_axis.powi(2) * self.semi_minor_axis).cbrt()
+	}
+}
+
+/// Serialized form of an ellipsoid, its defining semi-major axis (meters) and flattening
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct EllipsoidParameters {
+	semi_major_axis: f64,
+	flattening: f64
+}
+
+/// Serializes the defining semi-major axis and flattening only
+#[cfg(feature = "serde")]
+impl Serialize for GeoEllipsoid {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		EllipsoidParameters { semi_major_axis: self.semi_major_axis, flattening: self.flattening }.serialize(serializer)
+	}
+}
+
+/// Rebuilds the derived values through try_new, rejecting the parameters it rejects
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for GeoEllipsoid {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<GeoEllipsoid, D::Error> {
+		let parameters = EllipsoidParameters::deserialize(deserializer)?;
+		GeoEllipsoid::try_new(parameters.semi_major_axis, parameters.flattening).map_err(de::Error::custom)
+	}
+}
+
+/// WGS 84, the ellipsoid of GPS and most data
+impl Default for GeoEllipsoid {
+	fn default() -> GeoEllipsoid {
+		GeoEllipsoid::wgs84()
+	}
+}
+
+/// Ellipsoids are equal when their defining semi-major axis and flattening are
+impl PartialEq for GeoEllipsoid {
+	fn eq(&self, other: &GeoEllipsoid) -> bool {
+		self.semi_major_axis == other.semi_major_axis && self.flattening == other.flattening
+	}
+}
+
+/// Formats as "a=6378137.000 f=1/298.257223563", or "f=0" for a sphere
+impl fmt::Display for GeoEllipsoid {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.flattening == 0.0 {
+			write!(f, "a={:.3} f=0", self.semi_major_axis)
+		} else {
+			write!(f, "a={:.3} f=1/{:.9}", self.semi_major_axis, 1.0 / self.flattening)
+		}
+	}
+}
+
+/// Former name of GeoEllipsoid, kept so existing code compiles
+#[deprecated(note = "renamed to GeoEllipsoid")]
+#[allow(non_camel_case_types)]
+pub type geo_ellipsoid = GeoEllipsoid;
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use na::Vector3;
+	use geo;
+	#[test]
+	fn test_presets() {
+		//GRS80 semi-minor axis 6356752.314140 m
+		assert_approx_eq!(GeoEllipsoid::grs80().get_semi_minor_axis(), 6356752.314140, 1e-6);
+		assert_approx_eq!(GeoEllipsoid::wgs84().get_semi_minor_axis(), 6356752.314245, 1e-6);
+		//GRS80 and WGS84 share a and differ only in the flattening, by about 1.7e-11
+		let (grs80, wgs84) = (GeoEllipsoid::grs80(), GeoEllipsoid::wgs84());
+		assert_eq!(grs80.get_semi_major_axis(), wgs84.get_semi_major_axis());
+		let difference = grs80.get_flattening() - wgs84.get_flattening();
+		assert!(difference > 1.0e-11 && difference < 2.0e-11);
+		assert_approx_eq!(GeoEllipsoid::airy_1830().get_semi_minor_axis(), 6356256.909, 1e-3);
+		assert_approx_eq!(GeoEllipsoid::bessel_1841().get_semi_minor_axis(), 6356078.963, 1e-3);
+		assert_approx_eq!(GeoEllipsoid::clarke_1866().get_semi_minor_axis(), 6356583.8, 1e-3);
+		assert_approx_eq!(GeoEllipsoid::international_1924().get_semi_minor_axis(), 6356911.946, 1e-3);
+		assert_approx_eq!(GeoEllipsoid::krassovsky_1940().get_semi_minor_axis(), 6356863.019, 1e-3);
+		let sphere = GeoEllipsoid::authalic_sphere();
+		assert_eq!(sphere.get_semi_minor_axis(), 6371007.0);
+		assert_eq!((sphere.get_flattening(), sphere.get_first_ecc()), (0.0, 0.0));
+	}
+	#[test]
+	fn test_from_name() {
+		let same = |a: &GeoEllipsoid, b: &GeoEllipsoid| a.get_semi_major_axis() == b.get_semi_major_axis() && a.get_flattening() == b.get_flattening();
+		assert!(same(&GeoEllipsoid::from_name("GRS80").unwrap(), &GeoEllipsoid::grs80()));
+		assert!(same(&GeoEllipsoid::from_name("intl").unwrap(), &GeoEllipsoid::international_1924()));
+		assert!(same(&GeoEllipsoid::from_name("krass").unwrap(), &GeoEllipsoid::krassovsky_1940()));
+		assert!(same(&GeoEllipsoid::from_name("clrk66").unwrap(), &GeoEllipsoid::clarke_1866()));
+		assert!(same(&GeoEllipsoid::from_name("mod_airy").unwrap(), &GeoEllipsoid::airy_modified()));
+		//Case, separators and descriptive aliases
+		assert!(same(&GeoEllipsoid::from_name("grs80").unwrap(), &GeoEllipsoid::grs80()));
+		assert!(same(&GeoEllipsoid::from_name("GRS 1980").unwrap(), &GeoEllipsoid::grs80()));
+		assert!(same(&GeoEllipsoid::from_name("wgs-84").unwrap(), &GeoEllipsoid::wgs84()));
+		assert!(same(&GeoEllipsoid::from_name("BESSEL").unwrap(), &GeoEllipsoid::bessel_1841()));
+		assert!(same(&GeoEllipsoid::from_name("Bessel 1841").unwrap(), &GeoEllipsoid::bessel_1841()));
+		assert!(same(&GeoEllipsoid::from_name("Airy 1830").unwrap(), &GeoEllipsoid::airy_1830()));
+		assert!(same(&GeoEllipsoid::from_name("AUST_SA").unwrap(), &GeoEllipsoid::from_name("aust_SA").unwrap()));
+		assert!(GeoEllipsoid::from_name("bessel_nam").is_none());
+		assert!(GeoEllipsoid::from_name("").is_none());
+		assert!(GeoEllipsoid::from_name("mars").is_none());
+	}
+	#[test]
+	fn test_traits() {
+		let wgs84 = GeoEllipsoid::wgs84();
+		let copy = wgs84;
+		assert_eq!(copy.get_semi_major_axis(), wgs84.get_semi_major_axis());
+		assert_eq!(wgs84, GeoEllipsoid::new(WGS84_SEMI_MAJOR_AXIS_METERS, WGS84_FLATTENING));
+		assert_eq!(GeoEllipsoid::from_name("krass"), Some(GeoEllipsoid::krassovsky_1940()));
+		assert!(wgs84 != GeoEllipsoid::grs80());
+		assert_eq!(format!("{}", wgs84), "a=6378137.000 f=1/298.257223563");
+		assert_eq!(format!("{}", GeoEllipsoid::clarke_1866()), "a=6378206.400 f=1/294.978698200");
+		assert_eq!(format!("{}", GeoEllipsoid::authalic_sphere()), "a=6371007.000 f=0");
+		assert!(format!("{:?}", wgs84).starts_with("GeoEllipsoid { semi_major_axis: 6378137.0"));
+	}
+	#[test]
+	fn test_cached_constants() {
+		for ellipsoid in &[GeoEllipsoid::wgs84(), GeoEllipsoid::clarke_1866(), GeoEllipsoid::from_axes(6378206.4, 6356583.8), GeoEllipsoid::authalic_sphere()] {
+			assert_eq!(ellipsoid.get_first_ecc_squared(), ellipsoid.get_first_ecc().powi(2));
+			assert_eq!(ellipsoid.get_second_ecc_squared(), ellipsoid.get_second_ecc().powi(2));
+			assert_eq!(ellipsoid.get_axis_ratio_squared(), ellipsoid.get_semi_minor_axis().powi(2) / ellipsoid.get_semi_major_axis().powi(2));
+			assert_eq!(ellipsoid.get_one_minus_ecc_squared(), 1.0 - ellipsoid.get_first_ecc().powi(2));
+		}
+	}
+	#[test]
+	fn test_derived_getters() {
+		let wgs84 = GeoEllipsoid::wgs84();
+		assert_approx_eq!(wgs84.get_first_ecc_squared(), 6.69437999014e-3, 1e-14);
+		assert_approx_eq!(wgs84.get_second_ecc_squared(), 6.73949674228e-3, 1e-14);
+		assert_approx_eq!(wgs84.get_third_flattening(), 1.679220386383705e-3, 1e-15);
+		assert_approx_eq!(wgs84.get_third_flattening(),
+		                  (wgs84.get_semi_major_axis() - wgs84.get_semi_minor_axis()) / (wgs84.get_semi_major_axis() + wgs84.get_semi_minor_axis()), 1e-15);
+		//Published WGS84 mean radii
+		assert_approx_eq!(wgs84.get_mean_radius(), 6371008.771, 1e-3);
+		assert_approx_eq!(wgs84.get_authalic_radius(), 6371007.181, 1e-3);
+		assert_approx_eq!(wgs84.get_volumetric_radius(), 6371000.790, 1e-3);
+		let sphere = GeoEllipsoid::authalic_sphere();
+		assert_eq!((sphere.get_mean_radius(), sphere.get_authalic_radius(), sphere.get_volumetric_radius()), (6371007.0, 6371007.0, 6371007.0));
+		assert_eq!(sphere.get_third_flattening(), 0.0);
+	}
+	#[test]
+	fn test_radii_of_curvature() {
+		let wgs84 = GeoEllipsoid::wgs84();
+		//WGS84 tables: M, N and sqrt(M * N) at 0, 45 and 90 degrees
+		for &(lat, m, n, gaussian) in &[(0.0f64, 6335439.327, 6378137.000, 6356752.314),
+		                                (45.0, 6367381.816, 6388838.290, 6378101.030),
+		                                (90.0, 6399593.626, 6399593.626, 6399593.626)] {
+			let lat = lat.to_radians();
+			assert_approx_eq!(wgs84.radius_meridional(lat), m, 1e-3);
+			assert_approx_eq!(wgs84.radius_prime_vertical(lat), n, 1e-3);
+			assert_approx_eq!(wgs84.radius_gaussian(lat), gaussian, 1e-3);
+			assert_approx_eq!(wgs84.radius_in_azimuth(lat, 0.0), m, 1e-3);
+			assert_approx_eq!(wgs84.radius_in_azimuth(lat, 90.0f64.to_radians()), n, 1e-3);
+			assert_approx_eq!(wgs84.radius_in_azimuth(lat, 270.0f64.to_radians()), n, 1e-3);
+		}
+		//Between M and N, and even in latitude
+		let r = wgs84.radius_in_azimuth(0.6, 0.7);
+		assert!(r > wgs84.radius_meridional(0.6) && r < wgs84.radius_prime_vertical(0.6));
+		assert_eq!(wgs84.radius_meridional(-0.6), wgs84.radius_meridional(0.6));
+	}
+	#[test]
+	fn test_geocentric_radius() {
+		let wgs84 = GeoEllipsoid::wgs84();
+		assert_eq!(wgs84.geocentric_radius(0.0), WGS84_SEMI_MAJOR_AXIS_METERS);
+		assert_approx_eq!(wgs84.geocentric_radius(90.0f64.to_radians()), wgs84.get_semi_minor_axis(), 1e-6);
+		assert_approx_eq!(wgs84.geocentric_radius(-90.0f64.to_radians()), wgs84.get_semi_minor_axis(), 1e-6);
+		assert_approx_eq!(wgs84.geocentric_radius(45.0f64.to_radians()), 6367489.544, 1e-3);
+		for &lat in &[0.1f64, 0.5, 1.0, 1.5] {
+			assert_eq!(wgs84.geocentric_radius(-lat), wgs84.geocentric_radius(lat));
+		}
+		assert_eq!(GeoEllipsoid::authalic_sphere().geocentric_radius(0.7), 6371007.0);
+	}
+	#[test]
+	fn test_planetary_presets() {
+		//IAU polar radii recovered from the flattening
+		assert_approx_eq!(GeoEllipsoid::mars().get_semi_minor_axis(), 3376200.0, 1e-6);
+		assert_approx_eq!(GeoEllipsoid::mercury().get_semi_minor_axis(), 2438260.0, 1e-6);
+		assert_approx_eq!(GeoEllipsoid::jupiter().get_semi_minor_axis(), 66854000.0, 1e-6);
+		assert_approx_eq!(GeoEllipsoid::saturn().get_semi_minor_axis(), 54364000.0, 1e-6);
+		assert_approx_eq!(1.0 / GeoEllipsoid::mars().get_flattening(), 169.8944472236118, 1e-9);
+		assert_eq!(GeoEllipsoid::venus().get_semi_minor_axis(), 6051800.0);
+		//Round trip on the lunar sphere
+		let moon = GeoEllipsoid::moon();
+		for &lla in &[Vector3::new(0.3, -2.1, 1500.0), Vector3::new(-1.2, 0.4, -200.0), Vector3::new(0.0, 3.0, 0.0)] {
+			let ecef = geo::lla2ecef(&lla, &moon);
+			assert_approx_eq!(ecef.norm(), MOON_SEMI_MAJOR_AXIS_METERS + lla.z, 1e-8);
+			let back = geo::ecef2lla(&ecef, &moon);
+			assert_approx_eq!(back.x, lla.x, 1e-15);
+			assert_approx_eq!(back.y, lla.y, 1e-15);
+			assert_approx_eq!(back.z, lla.z, 1e-8);
+		}
+		//On the strongly flattened gas giants ecef2lla is exact on the surface only, ecef2lla_exact holds aloft
+		for &(giant, lat_error_250km, lat_error_5000km) in &[(GeoEllipsoid::jupiter(), 6.0e-9, 2.0e-6), (GeoEllipsoid::saturn(), 3.2e-8, 1.0e-5)] {
+			let mut worst = [0.0f64; 3];
+			for i in 0..=180 {
+				let lat = (i as f64 - 90.0).to_radians();
+				for (k, &alt) in [0.0, 250.0e3, 5000.0e3].iter().enumerate() {
+					let lla = Vector3::new(lat, 1.1, alt);
+					let ecef = geo::lla2ecef(&lla, &giant);
+					worst[k] = worst[k].max((geo::ecef2lla(&ecef, &giant).x - lat).abs());
+					let exact = geo::ecef2lla_exact(&ecef, &giant);
+					assert_approx_eq!(exact.x, lat, 1e-12);
+					assert_approx_eq!(exact.z, alt, 1e-5);
+				}
+			}
+			assert!(worst[0] < 1e-15);
+			assert!(worst[1] < lat_error_250km && worst[1] > lat_error_250km / 2.0);
+			assert!(worst[2] < lat_error_5000km && worst[2] > lat_error_5000km / 2.0);
+		}
+	}
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_serde() {
+		use serde_json;
+		for ellipsoid in &[GeoEllipsoid::wgs84(), GeoEllipsoid::bessel_1841(), GeoEllipsoid::moon(), GeoEllipsoid::from_axes(6378206.4, 6356583.8)] {
+			let json = serde_json::to_string(ellipsoid).unwrap();
+			let back: GeoEllipsoid = serde_json::from_str(&json).unwrap();
+			assert_eq!(&back, ellipsoid);
+			assert_approx_eq!(back.get_semi_minor_axis(), ellipsoid.get_semi_minor_axis(), 1e-8);
+			assert_approx_eq!(back.get_first_ecc(), ellipsoid.get_first_ecc(), 1e-15);
+			assert_approx_eq!(back.get_second_ecc(), ellipsoid.get_second_ecc(), 1e-15);
+			assert_approx_eq!(back.get_first_ecc_squared(), ellipsoid.get_first_ecc_squared(), 1e-15);
+			assert_approx_eq!(back.get_second_ecc_squared(), ellipsoid.get_second_ecc_squared(), 1e-15);
+			assert_approx_eq!(back.get_axis_ratio_squared(), ellipsoid.get_axis_ratio_squared(), 1e-15);
+			assert_approx_eq!(back.get_one_minus_ecc_squared(), ellipsoid.get_one_minus_ecc_squared(), 1e-15);
+		}
+		assert_eq!(serde_json::to_string(&GeoEllipsoid::moon()).unwrap(), r#"{"semi_major_axis":1737400.0,"flattening":0.0}"#);
+		assert!(serde_json::from_str::<GeoEllipsoid>(r#"{"semi_major_axis":-6378137.0,"flattening":0.0033}"#).is_err());
+		assert!(serde_json::from_str::<GeoEllipsoid>(r#"{"semi_major_axis":6378137.0,"flattening":1.5}"#).is_err());
+		assert!(serde_json::from_str::<GeoEllipsoid>(r#"{"semi_major_axis":6378137.0}"#).is_err());
+	}
+	#[test]
+	#[allow(deprecated)]
+	fn test_deprecated_alias() {
+		let ellipsoid: geo_ellipsoid = geo_ellipsoid::new(WGS84_SEMI_MAJOR_AXIS_METERS, WGS84_FLATTENING);
+		assert_eq!(ellipsoid, GeoEllipsoid::wgs84());
+		let converted = geo::lla2ecef(&Vector3::new(0.5, 0.5, 0.0), &ellipsoid);
+		assert_eq!(converted, geo::lla2ecef(&Vector3::new(0.5, 0.5, 0.0), &GeoEllipsoid::wgs84()));
+	}
+	#[test]
+	fn test_default() {
+		assert_eq!(GeoEllipsoid::default(), GeoEllipsoid::new(WGS84_SEMI_MAJOR_AXIS_METERS, WGS84_FLATTENING));
+		assert_eq!(GeoEllipsoid::default().get_semi_minor_axis(), GeoEllipsoid::wgs84().get_semi_minor_axis());
+	}
+	#[test]
+	fn test_try_new() {
+		let wgs84 = GeoEllipsoid::try_new(WGS84_SEMI_MAJOR_AXIS_METERS, 1.0 / WGS84_FLATTENING).unwrap();
+		assert_eq!(wgs84, GeoEllipsoid::wgs84());
+		assert_eq!(wgs84.get_semi_minor_axis(), GeoEllipsoid::wgs84().get_semi_minor_axis());
+		let sphere = GeoEllipsoid::try_new(6371000.0, 0.0).unwrap();
+		assert_eq!(sphere.get_semi_minor_axis(), 6371000.0);
+		assert_eq!(sphere.get_first_ecc(), 0.0);
+		assert_eq!(sphere.get_second_ecc(), 0.0);
+		assert_eq!(GeoEllipsoid::try_new(f64::NAN, 0.0), Err(EllipsoidError::NonFiniteInput));
+		assert_eq!(GeoEllipsoid::try_new(f64::INFINITY, 0.0), Err(EllipsoidError::NonFiniteInput));
+		assert_eq!(GeoEllipsoid::try_new(6378137.0, f64::NAN), Err(EllipsoidError::NonFiniteInput));
+		assert_eq!(GeoEllipsoid::try_new(6378137.0, f64::NEG_INFINITY), Err(EllipsoidError::NonFiniteInput));
+		assert_eq!(GeoEllipsoid::try_new(0.0, 0.003), Err(EllipsoidError::NonPositiveSemiMajorAxis));
+		assert_eq!(GeoEllipsoid::try_new(-6378137.0, 0.003), Err(EllipsoidError::NonPositiveSemiMajorAxis));
+		assert_eq!(GeoEllipsoid::try_new(6378137.0, -0.003), Err(EllipsoidError::FlatteningOutOfRange));
+		assert_eq!(GeoEllipsoid::try_new(6378137.0, 1.0), Err(EllipsoidError::FlatteningOutOfRange));
+		assert_eq!(GeoEllipsoid::try_new(6378137.0, 1.5), Err(EllipsoidError::FlatteningOutOfRange));
+		assert_eq!(EllipsoidError::FlatteningOutOfRange.to_string(), "flattening must lie in [0, 1)");
+	}
+	#[test]
+	fn test_try_from_inverse_flattening() {
+		let wgs84 = GeoEllipsoid::try_from_inverse_flattening(WGS84_SEMI_MAJOR_AXIS_METERS, WGS84_FLATTENING).unwrap();
+		assert_eq!(wgs84.get_semi_minor_axis(), GeoEllipsoid::wgs84().get_semi_minor_axis());
+		assert_eq!(wgs84.get_first_ecc(), GeoEllipsoid::wgs84().get_first_ecc());
+		let sphere = GeoEllipsoid::try_from_inverse_flattening(6371000.0, f64::INFINITY).unwrap();
+		assert_eq!(sphere.get_semi_minor_axis(), 6371000.0);
+		assert_eq!(sphere.get_first_ecc(), 0.0);
+		assert_eq!(GeoEllipsoid::try_from_inverse_flattening(6378137.0, f64::NAN), Err(EllipsoidError::NonFiniteInput));
+		assert_eq!(GeoEllipsoid::try_from_inverse_flattening(f64::NAN, 298.0), Err(EllipsoidError::NonFiniteInput));
+		assert_eq!(GeoEllipsoid::try_from_inverse_flattening(-6378137.0, 298.0), Err(EllipsoidError::NonPositiveSemiMajorAxis));
+		for inverse_flattening in [1.0, 0.5, 0.0, -0.0, -298.0].iter() {
+			assert_eq!(GeoEllipsoid::try_from_inverse_flattening(6378137.0, *inverse_flattening), Err(EllipsoidError::FlatteningOutOfRange));
+		}
+	}
+	#[test]
+	fn test_from_axes() {
+		let wgs84 = GeoEllipsoid::wgs84();
+		let from_axes = GeoEllipsoid::from_axes(wgs84.get_semi_major_axis(), wgs84.get_semi_minor_axis());
+		assert_eq!(from_axes.get_semi_major_axis(), wgs84.get_semi_major_axis());
+		assert_eq!(from_axes.get_semi_minor_axis(), wgs84.get_semi_minor_axis());
+		assert_eq!(from_axes.get_first_ecc(), wgs84.get_first_ecc());
+		assert_eq!(from_axes.get_second_ecc(), wgs84.get_second_ecc());
+		//The rounding of b, half an ulp of 6.4e6, limits the recovered flattening to about 1e-16
+		assert_approx_eq!(from_axes.get_flattening(), wgs84.get_flattening(), 1e-16);
+		assert_approx_eq!(1.0 / from_axes.get_flattening(), WGS84_FLATTENING, 1e-8);
+		//Clarke 1866 is defined by its axes
+		let clarke = GeoEllipsoid::from_axes(6378206.4, 6356583.8);
+		assert_approx_eq!(1.0 / clarke.get_flattening(), CLARKE_1866_FLATTENING, 1e-7);
+		let sphere = GeoEllipsoid::from_axes(6371000.0, 6371000.0);
+		assert_eq!((sphere.get_flattening(), sphere.get_first_ecc(), sphere.get_second_ecc()), (0.0, 0.0, 0.0));
+		assert!(GeoEllipsoid::from_axes(6356752.0, 6378137.0).get_first_ecc().is_nan());
+	}
 }
@@ -1 +1,4 @@
-pub mod geo_ellipsoid;
\ No newline at end of file
+pub mod geo_ellipsoid;
+pub mod utm;
+pub mod ups;
+pub mod triaxial_ellipsoid;
\ No newline at end of file
@@ -0,0 +1,143 @@
+use na::Vector3;
+use structs::geo_ellipsoid::GeoEllipsoid;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// Triaxial reference ellipsoid x^2 / a^2 + y^2 / b^2 + z^2 / c^2 = 1 of small bodies and irregular moons
+///
+/// The semi-axes a, b and c lie along x, y and z, conventionally a >= b >= c, with the longitude measured
+/// from the x axis. Planetodetic latitude and longitude are the direction of the surface normal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TriaxialEllipsoid {
+	a: f64,
+	b: f64,
+	c: f64
+}
+
+impl TriaxialEllipsoid {
+	/// Builds a triaxial ellipsoid from its semi-axes along x, y and z (meters), without validation
+	pub fn new(a: f64, b: f64, c: f64) -> TriaxialEllipsoid {
+		TriaxialEllipsoid {
+			a,
+			b,
+			c
+		}
+	}
+
+	/// Triaxial form of a biaxial ellipsoid, a = b = semi-major axis and c = semi-minor axis
+	pub fn from_biaxial(ellipsoid: &GeoEllipsoid) -> TriaxialEllipsoid {
+		TriaxialEllipsoid::new(ellipsoid.get_semi_major_axis(), ellipsoid.get_semi_major_axis(), ellipsoid.get_semi_minor_axis())
+	}
+
+	/// Semi-axis along x (meters)
+	pub fn get_a(&self) -> f64 {
+		self.a
+	}
+
+	/// Semi-axis along y (meters)
+	pub fn get_b(&self) -> f64 {
+		self.b
+	}
+
+	/// Semi-axis along z (meters)
+	pub fn get_c(&self) -> f64 {
+		self.c
+	}
+
+	/// Point of the surface whose normal points along a planetodetic latitude and longitude (radians),
+	/// (a^2 * nx, b^2 * ny, c^2 * nz) / sqrt(a^2 * nx^2 + b^2 * ny^2 + c^2 * nz^2) for the unit normal n (meters)
+	pub fn surface_point(&self, lat: f64, lon: f64) -> Vector3<f64> {
+		let n = Vector3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin());
+		let scaled = Vector3::new(self.a.powi(2) * n.x, self.b.powi(2) * n.y, self.c.powi(2) * n.z);
+		scaled / (scaled.x * n.x + scaled.y * n.y + scaled.z * n.z).sqrt()
+	}
+
+	/// Outward unit normal (x / a^2, y / b^2, z / c^2) normalized, at a point of the surface (meters)
+	///
+	/// Off the surface this is the normal of the similar ellipsoid through the point, not the direction to
+	/// the nearest surface point.
+	pub fn surface_normal(&self, point: &Vector3<f64>) -> Vector3<f64> {
+		let gradient = Vector3::new(point.x / self.a.powi(2), point.y / self.b.powi(2), point.z / self.c.powi(2));
+		gradient / gradient.norm()
+	}
+
+	/// Distance from the center to the surface in the direction of a planetocentric latitude and longitude (radians),
+	/// 1 / sqrt((cos(lat) * cos(lon) / a)^2 + (cos(lat) * sin(lon) / b)^2 + (sin(lat) / c)^2) (meters)
+	pub fn geocentric_radius(&self, lat: f64, lon: f64) -> f64 {
+		let (sin_lat, cos_lat) = lat.sin_cos();
+		((cos_lat * lon.cos() / self.a).powi(2) + (cos_lat * lon.sin() / self.b).powi(2) + (sin_lat / self.c).powi(2)).sqrt().recip()
+	}
+}
+
+/// Serialized form of a triaxial ellipsoid, its semi-axes along x, y and z (meters)
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct TriaxialParameters {
+	a: f64,
+	b: f64,
+	c: f64
+}
+
+/// Rejects semi-axes that are not finite and positive
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TriaxialEllipsoid {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TriaxialEllipsoid, D::Error> {
+		let parameters = TriaxialParameters::deserialize(deserializer)?;
+		if [parameters.a, parameters.b, parameters.c].iter().all(|axis| axis.is_finite() && *axis > 0.0) {
+			Ok(TriaxialEllipsoid::new(parameters.a, parameters.b, parameters.c))
+		} else {
+			Err(de::Error::custom("triaxial ellipsoid semi-axes must be finite and positive"))
+		}
+	}
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::f64;
+	#[test]
+	fn test_geocentric_radius() {
+		let ellipsoid = TriaxialEllipsoid::new(3000.0, 2000.0, 1000.0);
+		assert_eq!(ellipsoid.geocentric_radius(0.0, 0.0), 3000.0);
+		assert_approx_eq!(ellipsoid.geocentric_radius(0.0, f64::consts::FRAC_PI_2), 2000.0, 1e-9);
+		assert_eq!(ellipsoid.geocentric_radius(f64::consts::FRAC_PI_2, 0.3), 1000.0);
+		let (lat, lon) = (0.4f64, 1.1f64);
+		let r = ellipsoid.geocentric_radius(lat, lon);
+		let point = r * Vector3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin());
+		assert_approx_eq!((point.x / 3000.0).powi(2) + (point.y / 2000.0).powi(2) + (point.z / 1000.0).powi(2), 1.0, 1e-15);
+		//Matches the biaxial geocentric radius, which takes the geodetic latitude of the surface point
+		let wgs84 = GeoEllipsoid::wgs84();
+		let surface = TriaxialEllipsoid::from_biaxial(&wgs84).surface_point(0.7, 0.2);
+		let geocentric_lat = surface.z.atan2((surface.x.powi(2) + surface.y.powi(2)).sqrt());
+		assert_approx_eq!(TriaxialEllipsoid::from_biaxial(&wgs84).geocentric_radius(geocentric_lat, 0.2), wgs84.geocentric_radius(0.7), 1e-8);
+	}
+	#[test]
+	fn test_surface_point_and_normal() {
+		let ellipsoid = TriaxialEllipsoid::new(3000.0, 2000.0, 1000.0);
+		assert_eq!(ellipsoid.surface_point(0.0, 0.0), Vector3::new(3000.0, 0.0, 0.0));
+		assert_approx_eq!(ellipsoid.surface_point(0.0, -f64::consts::FRAC_PI_2).y, -2000.0, 1e-12);
+		assert_approx_eq!(ellipsoid.surface_point(f64::consts::FRAC_PI_2, 0.0).z, 1000.0, 1e-12);
+		for &(lat, lon) in [(0.4f64, 1.1f64), (-1.2, -2.5), (0.05, 3.0)].iter() {
+			let point = ellipsoid.surface_point(lat, lon);
+			assert_approx_eq!((point.x / 3000.0).powi(2) + (point.y / 2000.0).powi(2) + (point.z / 1000.0).powi(2), 1.0, 1e-15);
+			let normal = ellipsoid.surface_normal(&point);
+			assert_approx_eq!(normal.z.asin(), lat, 1e-14);
+			assert_approx_eq!(normal.y.atan2(normal.x), lon, 1e-14);
+		}
+		assert_eq!(ellipsoid.surface_normal(&Vector3::new(0.0, 0.0, -1000.0)), Vector3::new(0.0, 0.0, -1.0));
+	}
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_serde() {
+		use serde_json;
+		let ellipsoid = TriaxialEllipsoid::new(3000.0, 2000.0, 1000.0);
+		let json = serde_json::to_string(&ellipsoid).unwrap();
+		assert_eq!(json, r#"{"a":3000.0,"b":2000.0,"c":1000.0}"#);
+		assert_eq!(serde_json::from_str::<TriaxialEllipsoid>(&json).unwrap(), ellipsoid);
+		assert!(serde_json::from_str::<TriaxialEllipsoid>(r#"{"a":3000.0,"b":0.0,"c":1000.0}"#).is_err());
+		assert!(serde_json::from_str::<TriaxialEllipsoid>(r#"{"a":3000.0,"b":2000.0,"c":-1000.0}"#).is_err());
+		assert!(serde_json::from_str::<TriaxialEllipsoid>(r#"{"a":1e400,"b":2000.0,"c":1000.0}"#).is_err());
+	}
+}
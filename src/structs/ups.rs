@@ -0,0 +1,43 @@
+use structs::utm::Hemisphere;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const UPS_SCALE_FACTOR: f64 = 0.994;
+pub const UPS_FALSE_EASTING_METERS: f64 = 2000000.0;
+pub const UPS_FALSE_NORTHING_METERS: f64 = 2000000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UPS {
+	easting: f64,
+	northing: f64,
+	altitude: f64,
+	hemisphere: Hemisphere
+}
+
+impl UPS {
+	pub fn new(easting: f64, northing: f64, altitude: f64, hemisphere: Hemisphere) -> UPS {
+		UPS {
+			easting,
+			northing,
+			altitude,
+			hemisphere
+		}
+	}
+
+	pub fn get_easting(&self) -> f64 {
+		self.easting
+	}
+
+	pub fn get_northing(&self) -> f64 {
+		self.northing
+	}
+
+	pub fn get_altitude(&self) -> f64 {
+		self.altitude
+	}
+
+	pub fn get_hemisphere(&self) -> Hemisphere {
+		self.hemisphere
+	}
+}
@@ -0,0 +1,167 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const UTM_SCALE_FACTOR: f64 = 0.9996;
+pub const UTM_FALSE_EASTING_METERS: f64 = 500000.0;
+pub const UTM_FALSE_NORTHING_SOUTH_METERS: f64 = 10000000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Hemisphere {
+	North,
+	South
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UTM {
+	easting: f64,
+	northing: f64,
+	altitude: f64,
+	zone: u8,
+	hemisphere: Hemisphere,
+	band: Option<char>
+}
+
+/// Latitude band letters from 80 S to 84 N, 8 degrees each (X spans 12)
+const UTM_BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+
+impl UTM {
+	pub fn new(easting: f64, northing: f64, altitude: f64, zone: u8, hemisphere: Hemisphere) -> UTM {
+		UTM {
+			easting,
+			northing,
+			altitude,
+			zone,
+			hemisphere,
+			band: None
+		}
+	}
+
+	/// Creates UTM coordinates with a latitude band letter, deriving the hemisphere from the band
+	pub fn new_with_band(easting: f64, northing: f64, altitude: f64, zone: u8, band: char) -> UTM {
+		let hemisphere = if band.to_ascii_uppercase() >= 'N' { Hemisphere::North } else { Hemisphere::South };
+		UTM {
+			easting,
+			northing,
+			altitude,
+			zone,
+			hemisphere,
+			band: Some(band.to_ascii_uppercase())
+		}
+	}
+
+	/// UTM zone number (1 - 60) for a latitude and longitude in radians, including the
+	/// 32V widening over southwest Norway and the 31X/33X/35X/37X zones around Svalbard
+	pub fn zone_for(lat: f64, lon: f64) -> u8 {
+		let lat_deg = lat.to_degrees();
+		let mut lon_deg = lon.to_degrees() % 360.0;
+		if lon_deg > 180.0 {
+			lon_deg -= 360.0;
+		} else if lon_deg <= -180.0 {
+			lon_deg += 360.0;
+		}
+		if (56.0..64.0).contains(&lat_deg) && (3.0..12.0).contains(&lon_deg) {
+			return 32;
+		}
+		if (72.0..=84.0).contains(&lat_deg) && (0.0..42.0).contains(&lon_deg) {
+			return if lon_deg < 9.0 {
+				31
+			} else if lon_deg < 21.0 {
+				33
+			} else if lon_deg < 33.0 {
+				35
+			} else {
+				37
+			};
+		}
+		(((lon_deg + 180.0) / 6.0).floor() as i32 + 1).clamp(1, 60) as u8
+	}
+
+	/// Latitude band letter for a latitude in radians, or None outside 80 S to 84 N
+	pub fn band_for(lat: f64) -> Option<char> {
+		let lat_deg = lat.to_degrees();
+		if !(-80.0..=84.0).contains(&lat_deg) {
+			return None;
+		}
+		let index = (((lat_deg + 80.0) / 8.0).floor() as usize).min(UTM_BANDS.len() - 1);
+		Some(UTM_BANDS[index] as char)
+	}
+
+	pub fn get_easting(&self) -> f64 {
+		self.easting
+	}
+
+	pub fn get_northing(&self) -> f64 {
+		self.northing
+	}
+
+	pub fn get_altitude(&self) -> f64 {
+		self.altitude
+	}
+
+	pub fn get_zone(&self) -> u8 {
+		self.zone
+	}
+
+	pub fn get_hemisphere(&self) -> Hemisphere {
+		self.hemisphere
+	}
+
+	pub fn get_band(&self) -> Option<char> {
+		self.band
+	}
+
+	/// Longitude of the central meridian of the zone (radians)
+	pub fn get_central_meridian(&self) -> f64 {
+		(self.zone as f64 * 6.0 - 183.0).to_radians()
+	}
+}
+
+//Unit tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	#[test]
+	fn test_zone_for() {
+		assert_eq!(UTM::zone_for(48.856614f64.to_radians(), 2.352222f64.to_radians()), 31);
+		assert_eq!(UTM::zone_for(60.3913f64.to_radians(), 5.3221f64.to_radians()), 32);
+		assert_eq!(UTM::zone_for(60.3913f64.to_radians(), 2.9f64.to_radians()), 31);
+		assert_eq!(UTM::zone_for(64.0f64.to_radians(), 5.3221f64.to_radians()), 31);
+		assert_eq!(UTM::zone_for(78.0f64.to_radians(), 8.9f64.to_radians()), 31);
+		assert_eq!(UTM::zone_for(78.0f64.to_radians(), 9.0f64.to_radians()), 33);
+		assert_eq!(UTM::zone_for(78.0f64.to_radians(), 25.0f64.to_radians()), 35);
+		assert_eq!(UTM::zone_for(78.0f64.to_radians(), 40.0f64.to_radians()), 37);
+		assert_eq!(UTM::zone_for(78.0f64.to_radians(), 42.0f64.to_radians()), 38);
+		assert_eq!(UTM::zone_for(0.0, -180.0f64.to_radians()), 60);
+		assert_eq!(UTM::zone_for(0.0, -179.9f64.to_radians()), 1);
+		assert_eq!(UTM::zone_for(0.0, 180.0f64.to_radians()), 60);
+		assert_eq!(UTM::zone_for(0.0, 179.9f64.to_radians()), 60);
+	}
+	#[test]
+	fn test_band_for() {
+		assert_eq!(UTM::band_for(-80.0f64.to_radians()), Some('C'));
+		assert_eq!(UTM::band_for(-0.1f64.to_radians()), Some('M'));
+		assert_eq!(UTM::band_for(0.0), Some('N'));
+		assert_eq!(UTM::band_for(48.856614f64.to_radians()), Some('U'));
+		assert_eq!(UTM::band_for(84.0f64.to_radians()), Some('X'));
+		assert_eq!(UTM::band_for(84.5f64.to_radians()), None);
+		assert_eq!(UTM::band_for(-80.5f64.to_radians()), None);
+	}
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_serde() {
+		use serde_json;
+		let utm = UTM::new_with_band(452484.16, 5411718.72, 35.0, 31, 'U');
+		let back: UTM = serde_json::from_str(&serde_json::to_string(&utm).unwrap()).unwrap();
+		assert_eq!(back, utm);
+		assert_eq!(serde_json::to_string(&Hemisphere::South).unwrap(), r#""South""#);
+	}
+	#[test]
+	fn test_new_with_band() {
+		let utm = UTM::new_with_band(452484.16, 5411718.72, 0.0, 31, 'u');
+		assert_eq!(utm.get_band(), Some('U'));
+		assert_eq!(utm.get_hemisphere(), Hemisphere::North);
+		assert_eq!(UTM::new_with_band(334368.63, 6250948.35, 0.0, 56, 'H').get_hemisphere(), Hemisphere::South);
+	}
+}